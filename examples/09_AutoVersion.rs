@@ -14,14 +14,15 @@ fn main() {
     //                  option_env!("CARGO_PKG_VERSION_PRE").unwrap_or("")); 
     //
     // Starting from v0.6.6 on crates.io you can also use the crate_version!() macro instead of 
-    // manually using the env!() macros. Under the hood, the macro uses this exact method to get
-    // the version.
+    // manually using the env!() macros. Under the hood, the macro uses env!("CARGO_PKG_VERSION")
+    // which is a &'static str, so it can be passed to version() directly. The crate_authors!()
+    // and crate_description!() macros work the same way for author() and about().
     //
     // Thanks to https://github.com/jhelwig for pointing this out
     let matches = App::new("myapp")
                       .about("does awesome things")
                       // use crate_version! to pull the version number
-                      .version(&crate_version!()[..])
+                      .version(crate_version!())
                       .get_matches();
 
     // running the this app with the -v or --version will display whatever version is in your
@@ -3,18 +3,26 @@ extern crate clap;
 use clap::{App, Arg};
 
 fn main() {
-    // You can get a "default value" like feature by using Option<T>'s .unwrap_or() method
+    // You can set a default value for an option or positional argument with .default_value(),
+    // which is used whenever the user doesn't supply the argument.
     //
     // Let's assume you have -c <config> argument to allow users to specify a configuration file
     // but you also want to support a default file, if none is specified.
     let matches = App::new("myapp").about("does awesome things")
                         .arg(Arg::with_name("CONFIG")
-                                .help("The config file to use (default is \"config.json\")")
+                                .help("The config file to use")
                                 .short("c")
-                                .takes_value(true))
+                                .takes_value(true)
+                                .default_value("config.json"))
                         .get_matches();
 
-    let config_file = matches.value_of("CONFIG").unwrap_or("config.json");
+    // Because of the default value, this is always Some(..)
+    let config_file = matches.value_of("CONFIG").unwrap();
+
+    // You can also check whether the user supplied a value, or the default is used
+    if matches.is_default("CONFIG") {
+        println!("No config file specified, using {}", config_file);
+    }
 
     // use config_file here...
-}
\ No newline at end of file
+}
@@ -0,0 +1,38 @@
+/// The entire definition of a CLI (name, version, args, groups, subcommands) can live in a YAML
+/// file which is loaded at compile time with the `load_yaml!` macro and passed to
+/// `App::from_yaml()`. This keeps the Rust code free of builder calls, and allows those who don't
+/// know Rust to edit the command line interface.
+///
+/// This example requires the `yaml` cargo feature, i.e. `cargo run --example 15_FromYaml
+/// --features yaml`
+
+#[cfg(feature = "yaml")]
+#[macro_use]
+extern crate clap;
+
+#[cfg(feature = "yaml")]
+fn main() {
+    use clap::App;
+
+    // The YAML file is found relative to the current file, similar to how modules are found
+    let yml = load_yaml!("15_FromYaml.yml");
+    let matches = App::from_yaml(yml).get_matches();
+
+    // Matches are used exactly like they would be with an App built in Rust
+    println!("Using input file: {}", matches.value_of("input").unwrap());
+
+    if let Some(c) = matches.value_of("config") {
+        println!("Value for config: {}", c);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("test") {
+        if matches.is_present("list") {
+            println!("Printing testing lists...");
+        }
+    }
+}
+
+#[cfg(not(feature = "yaml"))]
+fn main() {
+    println!("This example requires the 'yaml' feature, try: cargo run --example 15_FromYaml --features yaml");
+}
@@ -5,13 +5,227 @@ use std::collections::HashMap;
 use std::env;
 use std::path::Path;
 use std::vec::IntoIter;
-use std::borrow::ToOwned;
+use std::borrow::{Cow, ToOwned};
 use std::process;
+use std::mem;
+use std::cell::RefCell;
 use std::fmt::Write;
+use std::io;
+use std::ffi::OsString;
+use std::str::FromStr;
 
 use args::{ ArgMatches, Arg, SubCommand, MatchedArg};
 use args::{ FlagBuilder, OptBuilder, PosBuilder};
 use args::ArgGroup;
+use partial::PartialParser;
+use suggestions::did_you_mean;
+use quote::quote;
+use osstr;
+
+#[cfg(feature = "yaml")]
+use yaml_rust::Yaml;
+#[cfg(feature = "timings")]
+use std::time::Instant;
+#[cfg(feature = "timings")]
+use timings::{Phase, TimingHook};
+
+/// Determines how the usage string of a subcommand relates to the custom usage string of its
+/// parent (set with `App::usage()`). This is set on the subcommand with `App::parent_usage()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParentUsage {
+    /// The subcommand generates its own usage string starting with the full binary name, i.e.
+    /// `myprog test [FLAGS]` (the default)
+    Ignore,
+    /// The subcommand displays the parent's custom usage string as-is
+    Inherit,
+    /// The subcommand generates its own usage string, but starting with the parent's custom usage
+    /// string followed by the subcommand's name instead of the full binary name, i.e.
+    /// `myprog [-clDas] test [FLAGS]`
+    Prefix
+}
+
+/// Behavioral toggles of an `App`, set with `App::setting()` (or `App::global_setting()` to also
+/// apply them to all subcommands). Each setting is off by default.
+///
+///
+/// # Example
+///
+/// ```no_run
+/// # use clap::{App, AppSettings};
+/// let matches = App::new("myprog")
+///                   .setting(AppSettings::SubcommandRequired)
+///                   .global_setting(AppSettings::AllowNegativeNumbers)
+///                   .get_matches();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AppSettings {
+    /// Omits the subcommand from its parent's help information, see `App::hidden()`
+    Hidden,
+    /// Adds the version flags of the parent to all subcommands, see `App::global_version()`
+    GlobalVersion,
+    /// Accepts negative numbers as values, see `App::allow_negative_numbers()`
+    AllowNegativeNumbers,
+    /// Captures all remaining arguments with the last positional argument once it's reached, see
+    /// `App::trailing_var_arg()`
+    TrailingVarArg,
+    /// Treats all arguments after `--` as positional arguments, even those matching the name of a
+    /// subcommand, see `App::positional_after_separator()`
+    PositionalAfterSeparator,
+    /// Captures unknown subcommands and their arguments, see `App::allow_external_subcommands()`
+    AllowExternalSubcommands,
+    /// Accepts arguments of external subcommands which aren't valid UTF-8, see
+    /// `App::allow_invalid_utf8_for_external_subcommands()`
+    AllowInvalidUtf8ForExternalSubcommands,
+    /// Displays the help (or version) even if there are errors, see `App::help_overrides_errors()`
+    HelpOverridesErrors,
+    /// Accepts unambiguous prefixes of subcommands, see `App::infer_subcommands()`
+    InferSubcommands,
+    /// Accepts unambiguous prefixes of long arguments, see `App::infer_long_args()`
+    InferLongArgs,
+    /// Disables inference and aliases for this app and its subcommands, see `App::strict()`
+    Strict,
+    /// Displays the help if no arguments were supplied, see `App::arg_required_else_help()`
+    ArgRequiredElseHelp,
+    /// Displays only the error message on errors, see `App::dont_print_full_usage_on_error()`
+    DontPrintFullUsageOnError,
+    /// Lists the conflicts and requirements of each argument in the long help, see
+    /// `App::describe_relations()`
+    DescribeRelations,
+    /// Lists the flags and options together in a single OPTIONS section of the help, see
+    /// `App::unified_help_message()`
+    UnifiedHelpMessage,
+    /// Reports an error if none of the subcommands were used at runtime
+    SubcommandRequired,
+    /// Doesn't generate the `-h`/`--help` flags, i.e. for apps which implement their own help
+    DisableHelpFlag,
+    /// Doesn't generate the `-v`/`--version` flags
+    DisableVersionFlag,
+    /// Doesn't generate the `help` subcommand
+    DisableHelpSubcommand
+}
+
+impl FromStr for AppSettings {
+    type Err = String;
+
+    // The names are matched case insensitively, and may be written in snake_case (i.e. in YAML)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.replace("_", "").to_lowercase()[..] {
+            "hidden"                                 => Ok(AppSettings::Hidden),
+            "globalversion"                          => Ok(AppSettings::GlobalVersion),
+            "allownegativenumbers"                   => Ok(AppSettings::AllowNegativeNumbers),
+            "trailingvararg"                         => Ok(AppSettings::TrailingVarArg),
+            "positionalafterseparator"               => Ok(AppSettings::PositionalAfterSeparator),
+            "allowexternalsubcommands"               => Ok(AppSettings::AllowExternalSubcommands),
+            "allowinvalidutf8forexternalsubcommands" => Ok(AppSettings::AllowInvalidUtf8ForExternalSubcommands),
+            "helpoverrideserrors"                    => Ok(AppSettings::HelpOverridesErrors),
+            "infersubcommands"                       => Ok(AppSettings::InferSubcommands),
+            "inferlongargs"                          => Ok(AppSettings::InferLongArgs),
+            "strict"                                 => Ok(AppSettings::Strict),
+            "argrequiredelsehelp"                    => Ok(AppSettings::ArgRequiredElseHelp),
+            "dontprintfullusageonerror"              => Ok(AppSettings::DontPrintFullUsageOnError),
+            "describerelations"                      => Ok(AppSettings::DescribeRelations),
+            "unifiedhelpmessage"                     => Ok(AppSettings::UnifiedHelpMessage),
+            "subcommandrequired"                     => Ok(AppSettings::SubcommandRequired),
+            "disablehelpflag"                        => Ok(AppSettings::DisableHelpFlag),
+            "disableversionflag"                     => Ok(AppSettings::DisableVersionFlag),
+            "disablehelpsubcommand"                  => Ok(AppSettings::DisableHelpSubcommand),
+            _                                        => Err(format!("{} isn't a valid setting", s))
+        }
+    }
+}
+
+// The rendered sections of the help message which list the arguments and subcommands
+#[derive(Clone)]
+struct ArgsHelp {
+    flags: String,
+    opts: String,
+    pos: String,
+    subcmds: String,
+    // The title and contents of the section of each custom heading (of arguments or groups)
+    groups: Vec<(String, String)>
+}
+
+/// Splits authors separated by colons, as Cargo does, into the individual authors. Colons followed
+/// by `//` or within `<...>` aren't separators, so URLs and addresses (i.e. `<mailto:a@b.c>`)
+/// stay intact. This is used by `App::author()` and the `crate_authors!` macro.
+#[doc(hidden)]
+pub fn split_authors(a: &str) -> Vec<&str> {
+    let mut authors = vec![];
+    let mut start = 0;
+    let mut in_brackets = false;
+    for (i, c) in a.char_indices() {
+        match c {
+            '<' => in_brackets = true,
+            '>' => in_brackets = false,
+            ':' if !in_brackets && !a[i + 1..].starts_with("//") => {
+                authors.push(a[start..i].trim());
+                start = i + 1;
+            },
+            _   => ()
+        }
+    }
+    authors.push(a[start..].trim());
+    authors.into_iter().filter(|a| !a.is_empty()).collect()
+}
+
+/// Fails to compile (when evaluated in a `const`) if a subcommand name is given more than once.
+/// This is used by the `match_subcommands!` macro to reject arms repeating a name.
+#[doc(hidden)]
+pub const fn assert_unique_subcommands(names: &[&str]) {
+    let mut i = 0;
+    while i < names.len() {
+        let mut j = i + 1;
+        while j < names.len() {
+            let (a, b) = (names[i].as_bytes(), names[j].as_bytes());
+            let mut same = a.len() == b.len();
+            let mut k = 0;
+            while same && k < a.len() {
+                same = a[k] == b[k];
+                k += 1;
+            }
+            if same {
+                panic!("match_subcommands! has more than one arm for the same subcommand");
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+// Why the program exits, which determines its status code
+#[derive(Clone, Copy)]
+enum Exit {
+    Help,
+    Version,
+    Error
+}
+
+// Replaces each "{name}" token of a template with its value in a single pass, so that a value
+// containing a token (i.e. an about message mentioning "{usage}") is written as is. Unknown
+// tokens are left as they are.
+fn fill_template(t: &str, values: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(t.len());
+    let mut rest = t;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let value = rest.find('}').and_then(|end| {
+            values.iter().filter(|&&(name, _)| name == &rest[1..end]).next().map(|&(_, v)| (v, end))
+        });
+        match value {
+            Some((v, end)) => {
+                out.push_str(v);
+                rest = &rest[end + 1..];
+            },
+            None => {
+                out.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
 
 /// Used to create a representation of a command line program and all possible command line
 /// arguments for parsing at runtime.
@@ -42,38 +256,107 @@ use args::ArgGroup;
 /// ```
 pub struct App<'a, 'v, 'ab, 'u, 'h, 'ar> {
     // The name displayed to the user when showing version and help/usage information
-    name: String,
+    pub(crate) name: String,
     name_slice: &'ar str,
     // A string of author(s) if desired. Displayed when showing help/usage information
-    author: Option<&'a str>,
+    pub(crate) author: Option<&'a str>,
+    // Displayed between multiple authors, which are separated by colons in `author`
+    pub(crate) author_sep: Option<&'a str>,
     // The version displayed to the user
-    version: Option<&'v str>,
+    pub(crate) version: Option<&'v str>,
+    // A detailed version displayed instead of `version` with --version
+    long_version: Option<&'v str>,
+    // Build metadata (i.e. commit hash, target triple, etc.) displayed with the version
+    version_meta: Vec<(&'v str, &'v str)>,
+    // A template used to lay out the version line
+    version_template: Option<&'v str>,
     // A brief explaination of the program that gets displayed to the user when shown help/usage information
-    about: Option<&'ab str>,
+    pub(crate) about: Option<&'ab str>,
+    // A detailed explaination of the program that gets displayed instead of `about` with --help
+    pub(crate) long_about: Option<&'ab str>,
     // Additional help information
-    more_help: Option<&'h str>,
+    pub(crate) more_help: Option<&'h str>,
+    // Additional help information displayed instead of `more_help` with --help
+    pub(crate) after_long_help: Option<&'h str>,
+    // Help information displayed before the auto-generated help, and its detailed version for
+    // --help
+    before_help: Option<&'h str>,
+    before_long_help: Option<&'h str>,
+    // A template used to lay out the help information
+    help_template: Option<&'h str>,
+    // A custom help message which replaces the auto-generated one entirely
+    help_str: Option<&'h str>,
+    // Whether the long help lists the conflicts and requirements of each argument
+    help_relations: bool,
+    // Whether the flags and options are listed together in the help, see unified_help_message()
+    unified_help: bool,
     // A list of possible flags
-    flags: BTreeMap<&'ar str, FlagBuilder<'ar>>,
+    pub(crate) flags: BTreeMap<&'ar str, FlagBuilder<'ar>>,
     // A list of possible options
-    opts: BTreeMap<&'ar str, OptBuilder<'ar>>,
+    pub(crate) opts: BTreeMap<&'ar str, OptBuilder<'ar>>,
     // A list of positional arguments
-    positionals_idx: BTreeMap<u8, PosBuilder<'ar>>,
+    pub(crate) positionals_idx: BTreeMap<u8, PosBuilder<'ar>>,
     // A list of subcommands
-    subcommands: BTreeMap<String, App<'a, 'v, 'ab, 'u, 'h, 'ar>>,
-    needs_long_help: bool,
-    needs_long_version: bool,
-    needs_short_help: bool,
-    needs_short_version: bool,
+    pub(crate) subcommands: BTreeMap<String, App<'a, 'v, 'ab, 'u, 'h, 'ar>>,
+    // Whether this subcommand is omitted from its parent's help information
+    pub(crate) hidden: bool,
+    pub(crate) needs_long_help: bool,
+    pub(crate) needs_long_version: bool,
+    pub(crate) needs_short_help: bool,
+    pub(crate) needs_short_version: bool,
     needs_subcmd_help: bool,
+    // Whether the help (or version) flags were disabled explicitly, rather than taken over
+    disable_help: bool,
+    disable_version: bool,
+    // The customizations of the automatic help and version flags, see mut_arg()
+    help_arg: Option<Arg<'ar, 'ar, 'ar, 'ar, 'ar, 'ar>>,
+    version_arg: Option<Arg<'ar, 'ar, 'ar, 'ar, 'ar, 'ar>>,
     required: HashSet<&'ar str>,
     matched_reqs: HashSet<&'ar  str>,
     arg_list: HashSet<&'ar str>,
     short_list: HashSet<char>,
     long_list: HashSet<&'ar str>,
     blacklist: HashSet<&'ar str>,
+    // The arguments which conflict with all others except the listed ones, which are turned into
+    // regular conflicts once all arguments are known
+    conflicts_except: Vec<(&'ar str, Vec<&'ar str>)>,
     usage_str: Option<&'u str>,
+    global_version: bool,
+    pos_after_sep: bool,
+    arg_required_else_help: bool,
+    allow_external_subcmds: bool,
+    allow_invalid_utf8_ext: bool,
+    // Whether arguments which aren't valid UTF-8 are reported as an error, see get_matches()
+    utf8_args: bool,
+    // The index of the argument being parsed, where the binary name is 0
+    cur_idx: usize,
+    // The names of the subcommands used to reach this one (including its own), if it's a
+    // subcommand
+    subcmd_path: Vec<String>,
+    help_overrides_errors: bool,
+    infer_subcmds: bool,
+    infer_long_args: bool,
+    allow_neg_nums: bool,
+    strict: bool,
+    trailing_var_arg: bool,
+    pub(crate) subcmd_required: bool,
+    // The settings which also apply to all subcommands, see global_setting()
+    g_settings: Vec<AppSettings>,
+    before_parse: Option<fn()>,
+    error_output: Option<fn(&str)>,
+    after_parse: Option<fn(&ArgMatches)>,
+    #[cfg(feature = "timings")]
+    timing_hook: Option<TimingHook>,
+    parent_usage: ParentUsage,
+    usage_prefix: Option<String>,
+    quiet_errors: bool,
+    error_code: Option<u8>,
+    help_code: Option<u8>,
+    version_code: Option<u8>,
     bin_name: Option<String>,
-    groups: HashMap<&'ar str, ArgGroup<'ar, 'ar>>
+    groups: HashMap<&'ar str, ArgGroup<'ar, 'ar>>,
+    global_args: Vec<Arg<'ar, 'ar, 'ar, 'ar, 'ar, 'ar>>,
+    help_cache: RefCell<HashMap<bool, ArgsHelp>>
 }
 
 impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
@@ -94,32 +377,191 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
             name: n.to_owned(),
             name_slice: n,
             author: None,
+            author_sep: None,
             about: None,
+            long_about: None,
             more_help: None,
+            after_long_help: None,
+            before_help: None,
+            before_long_help: None,
+            help_template: None,
+            help_str: None,
+            help_relations: false,
+            unified_help: false,
             version: None,
+            long_version: None,
+            version_meta: vec![],
+            version_template: None,
             flags: BTreeMap::new(),
             opts: BTreeMap::new(),
             positionals_idx: BTreeMap::new(),
             subcommands: BTreeMap::new(),
+            hidden: false,
             needs_long_version: true,
             needs_long_help: true,
             needs_short_help: true,
             needs_subcmd_help: true,
             needs_short_version: true,
+            disable_help: false,
+            disable_version: false,
+            help_arg: None,
+            version_arg: None,
             required: HashSet::new(), 
             matched_reqs: HashSet::new(),
             arg_list: HashSet::new(),
             short_list: HashSet::new(),
             long_list: HashSet::new(),
             usage_str: None,
+            global_version: false,
+            pos_after_sep: false,
+            arg_required_else_help: false,
+            allow_external_subcmds: false,
+            allow_invalid_utf8_ext: false,
+            utf8_args: false,
+            cur_idx: 0,
+            subcmd_path: vec![],
+            help_overrides_errors: false,
+            infer_subcmds: false,
+            infer_long_args: false,
+            allow_neg_nums: false,
+            strict: false,
+            trailing_var_arg: false,
+            subcmd_required: false,
+            g_settings: vec![],
+            before_parse: None,
+            error_output: None,
+            after_parse: None,
+            #[cfg(feature = "timings")]
+            timing_hook: None,
+            parent_usage: ParentUsage::Ignore,
+            usage_prefix: None,
+            quiet_errors: false,
+            error_code: None,
+            help_code: None,
+            version_code: None,
             blacklist: HashSet::new(),
+            conflicts_except: vec![],
             bin_name: None,
             groups: HashMap::new(),
+            global_args: vec![],
+            help_cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Creates a new instance of an application from a YAML document (typically loaded with the
+    /// `load_yaml!` macro). This allows the entire definition of the command line interface to
+    /// live in a YAML file instead of Rust code.
+    ///
+    /// The document must be a hash containing at least a `name`, and may contain `version`,
+    /// `author`, `author_separator`, `about`, `after_help`, `usage`, `settings`,
+    /// `global_settings`, `args`, `groups`, and `subcommands`. `settings` and `global_settings`
+    /// are lists of `AppSettings` names (i.e. `subcommand_required`). `args`, `groups`, and
+    /// `subcommands` are lists, where each entry is a hash with a single key (the name) whose
+    /// value is a hash of settings (see `Arg::from_yaml()` and `ArgGroup::from_yaml()`).
+    /// Subcommands support the same keys as the document itself, except for `name`.
+    ///
+    /// **NOTE:** This requires the `yaml` cargo feature
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # #[macro_use]
+    /// # extern crate clap;
+    /// # use clap::App;
+    /// # fn main() {
+    /// // In cli.yml:
+    /// //
+    /// // name: myprog
+    /// // version: "1.0"
+    /// // about: Does awesome things
+    /// // args:
+    /// //     - config:
+    /// //         short: c
+    /// //         long: config
+    /// //         takes_value: true
+    /// //         help: Sets a custom config file
+    /// // subcommands:
+    /// //     - test:
+    /// //         about: controls testing features
+    /// let yml = load_yaml!("cli.yml");
+    /// let matches = App::from_yaml(yml).get_matches();
+    /// # }
+    /// ```
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(y: &'ar Yaml) -> App<'ar, 'ar, 'ar, 'ar, 'ar, 'ar> {
+        let name = y["name"].as_str().expect("App in YAML must have a \"name\" string");
+        App::from_yaml_named(name, y)
+    }
+
+    #[cfg(feature = "yaml")]
+    fn from_yaml_named(name: &'ar str, y: &'ar Yaml) -> App<'ar, 'ar, 'ar, 'ar, 'ar, 'ar> {
+        let mut a = App::new(name);
+        let settings = match y.as_hash() {
+            Some(h) => h,
+            None    => return a
+        };
+        for (k, v) in settings.iter() {
+            let k = k.as_str().expect("App setting names in YAML must be strings");
+            let s = || match *v {
+                // Unquoted versions such as 1.0 are parsed as floats
+                Yaml::String(ref s) | Yaml::Real(ref s) => &s[..],
+                _ => panic!("App \"{}\" YAML setting \"{}\" must be a string", name, k)
+            };
+            let l = || v.as_vec().unwrap_or_else(|| panic!("App \"{}\" YAML setting \"{}\" must be a list", name, k));
+            a = match k {
+                "name"         => a,
+                "version"      => a.version(s()),
+                "long_version" => a.long_version(s()),
+                "author"       => a.author(s()),
+                "settings"     => {
+                    for sy in l().iter() {
+                        let st = sy.as_str().unwrap_or_else(|| panic!("App \"{}\" YAML setting \"settings\" must be a list of strings", name));
+                        a = a.setting(st.parse().unwrap_or_else(|e| panic!("App \"{}\" YAML setting \"settings\" is invalid: {}", name, e)));
+                    }
+                    a
+                },
+                "global_settings" => {
+                    for sy in l().iter() {
+                        let st = sy.as_str().unwrap_or_else(|| panic!("App \"{}\" YAML setting \"global_settings\" must be a list of strings", name));
+                        a = a.global_setting(st.parse().unwrap_or_else(|e| panic!("App \"{}\" YAML setting \"global_settings\" is invalid: {}", name, e)));
+                    }
+                    a
+                },
+                "author_separator" => a.author_separator(s()),
+                "about"        => a.about(s()),
+                "long_about"   => a.long_about(s()),
+                "after_help"   => a.after_help(s()),
+                "after_long_help" => a.after_long_help(s()),
+                "before_help"  => a.before_help(s()),
+                "before_long_help" => a.before_long_help(s()),
+                "usage"        => a.usage(s()),
+                "args"         => a.args(l().iter().map(|ay| Arg::from_yaml(ay)).collect()),
+                "groups"       => a.arg_groups(l().iter().map(|gy| ArgGroup::from_yaml(gy)).collect()),
+                "subcommands"  => {
+                    for sy in l().iter() {
+                        let (sc_name, sc) = match sy.as_hash().and_then(|h| if h.len() == 1 { h.iter().next() } else { None }) {
+                            Some((n, sc)) => (n.as_str().expect("Subcommand name in YAML must be a string"), sc),
+                            None          => panic!("Subcommand in YAML must be a hash with a single key (the name of the subcommand)")
+                        };
+                        a = a.subcommand(App::from_yaml_named(sc_name, sc));
+                    }
+                    a
+                },
+                _              => panic!("Unknown setting \"{}\" for App \"{}\" in YAML", k, name)
+            };
         }
+        a
     }
 
     /// Sets a string of author(s) and will be displayed to the user when they request the version
-    /// or help information.
+    /// or help information. Multiple authors may be separated by colons (as Cargo does in
+    /// `CARGO_PKG_AUTHORS`, see `crate_authors!()`), in which case each author is displayed on its
+    /// own line (see `author_separator()`).
+    ///
+    /// **NOTE:** Colons which are followed by `//` (i.e. in `https://`) or within `<...>` (i.e. in
+    /// `<mailto:kevin@example.com>`) don't separate authors
+    ///
     ///
     /// # Example
     ///
@@ -134,6 +576,36 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
         self
     }
 
+    /// Sets the string displayed between multiple authors (separated by colons in `author()`) in
+    /// the help information and the `{author}` placeholder of templates. The default is a newline,
+    /// which displays each author on its own line.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let app = App::new("myprog")
+    /// .author("Kevin <kbknapp@gmail.com>:Bob <bob@example.com>")
+    /// .author_separator(", ")
+    /// # .get_matches();
+    /// ```
+    pub fn author_separator(mut self, s: &'a str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.author_sep = Some(s);
+        self
+    }
+
+    // The individual authors set with author(), see split_authors()
+    pub(crate) fn authors(&self) -> Vec<&'a str> {
+        self.author.map_or(vec![], split_authors)
+    }
+
+    // The authors joined by the separator set with author_separator()
+    fn author_str(&self) -> String {
+        let sep = self.author_sep.unwrap_or("\n");
+        self.authors().join(sep)
+    }
+
     /// Sets a string briefly describing what the program does and will be displayed when
     /// displaying help information.
     ///
@@ -150,62 +622,1039 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
         self
     }
 
-    /// Adds additional help information to be displayed in addition to and directly after
-    /// auto-generated help. This information is displayed **after** the auto-generated help
-    /// information. This additional help is often used to describe how to use the arguments,
-    /// or caveats to be noted.
+    /// Sets a detailed description of what the program does, which may span multiple
+    /// paragraphs. It is displayed instead of the text set with `about()` when the user requests
+    /// `--help`, while `-h` continues to display the brief description.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let app = App::new("myprog")
+    /// .about("Does really amazing things to great people")
+    /// .long_about("Does really amazing things to great people.
+    ///
+    /// Amazing things include, but are not limited to, being helpful.")
+    /// # .get_matches();
+    /// ```
+    pub fn long_about(mut self, a: &'ab str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.long_about = Some(a);
+        self
+    }
+
+    /// Lists the arguments each argument conflicts with and requires after its help message when
+    /// the user requests `--help` (i.e. `[conflicts with: --json; requires: --output]`), so these
+    /// don't need to be repeated in the help text by hand. The brief help displayed with `-h`
+    /// isn't affected.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let app = App::new("myprog")
+    /// .describe_relations(true)
+    /// .arg(Arg::from_usage("--yaml 'Prints YAML'").conflicts_with("json"))
+    /// .arg(Arg::from_usage("--json 'Prints JSON'"))
+    /// # .get_matches();
+    /// ```
+    pub fn describe_relations(mut self, d: bool) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.help_relations = d;
+        self
+    }
+
+    /// Lists the flags and options together in a single, alphabetized OPTIONS section of the help
+    /// message, instead of separate FLAGS and OPTIONS sections.
+    ///
+    /// **NOTE:** With a help template, `{options}` then lists both, and `{flags}` is empty
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let app = App::new("myprog")
+    /// .unified_help_message(true)
+    /// .arg(Arg::from_usage("-v --verbose 'Prints more details'"))
+    /// .arg(Arg::from_usage("-o --output [FILE] 'Writes the results to FILE'"))
+    /// # .get_matches();
+    /// ```
+    pub fn unified_help_message(mut self, u: bool) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.unified_help = u;
+        self
+    }
+
+    /// Adds additional help information to be displayed in addition to and directly after
+    /// auto-generated help. This information is displayed **after** the auto-generated help
+    /// information. This additional help is often used to describe how to use the arguments,
+    /// or caveats to be noted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .after_help("Does really amazing things to great people")
+    /// # .get_matches();
+    /// ```
+    pub fn after_help(mut self, h: &'h str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.more_help = Some(h);
+        self
+    }
+
+    /// Sets additional help information which is displayed after the auto-generated help when
+    /// requested with `--help` (or the `help` subcommand), instead of the one set with
+    /// `after_help()`. This allows the brief help displayed with `-h` to simply point to `--help`
+    /// for the details.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .after_help("See --help for examples")
+    /// .after_long_help("EXAMPLES:\n    myprog --verbose input.txt")
+    /// # .get_matches();
+    /// ```
+    pub fn after_long_help(mut self, h: &'h str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.after_long_help = Some(h);
+        self
+    }
+
+    /// Sets help information which is displayed before the auto-generated help (i.e. a notice or
+    /// banner).
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .before_help("Part of the myproject suite")
+    /// # .get_matches();
+    /// ```
+    pub fn before_help(mut self, h: &'h str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.before_help = Some(h);
+        self
+    }
+
+    /// Sets help information which is displayed before the auto-generated help when requested
+    /// with `--help` (or the `help` subcommand), instead of the one set with `before_help()`.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .before_help("Part of the myproject suite")
+    /// .before_long_help("Part of the myproject suite, see https://example.com/myproject")
+    /// # .get_matches();
+    /// ```
+    pub fn before_long_help(mut self, h: &'h str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.before_long_help = Some(h);
+        self
+    }
+
+    /// Sets a template used to lay out the auto-generated help information, allowing full control
+    /// over the ordering and wording of each section. The following placeholders are replaced
+    /// with their respective values:
+    ///
+    /// * `{bin}` - the binary name
+    /// * `{version}` - the version number
+    /// * `{author}` - the author(s)
+    /// * `{about}` - the description set with `about()`
+    /// * `{usage}` - the usage statement (without the leading `USAGE:`)
+    /// * `{all-args}` - all arguments and subcommands, in their default sections with titles
+    /// * `{flags}` - the flags, without a section title
+    /// * `{options}` - the options, without a section title
+    /// * `{positionals}` - the positional arguments, without a section title
+    /// * `{subcommands}` - the subcommands, without a section title
+    /// * `{before-help}` - the help information set with `before_help()` (or `before_long_help()`
+    /// for `--help`)
+    /// * `{after-help}` - the additional help information set with `after_help()` (or
+    /// `after_long_help()` for `--help`)
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .help_template("{bin} ({version}) - {about}
+    ///
+    /// USAGE: {usage}
+    ///
+    /// {all-args}")
+    /// # .get_matches();
+    /// ```
+    pub fn help_template(mut self, t: &'h str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.help_template = Some(t);
+        self
+    }
+
+    /// Replaces the entire auto-generated help information with a custom, hand-crafted, help
+    /// message. This message is displayed as-is for `-h`, `--help`, and the `help` subcommand,
+    /// while parsing, version information, and error messages work as usual.
+    ///
+    /// **NOTE:** Since the message is displayed as-is, it's up to you to keep it in sync with the
+    /// arguments actually supported.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .override_help("myprog v1.0
+    ///
+    /// USAGE: myprog [-d]
+    ///
+    ///     -d    Turns on debugging
+    ///     -h    Prints this help page")
+    /// # .get_matches();
+    /// ```
+    pub fn override_help(mut self, h: &'h str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.help_str = Some(h);
+        self
+    }
+
+    /// Sets a string of the version number to be displayed when displaying version or help
+    /// information.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let app = App::new("myprog")
+    /// .version("v0.1.24")
+    /// # .get_matches();
+    /// ```
+    pub fn version(mut self, v: &'v str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar>  {
+        self.version = Some(v);
+        self
+    }
+
+    /// Sets a detailed version string, which may span multiple lines (such as one including the
+    /// build date or enabled features). It is displayed instead of the version set with
+    /// `version()` when the user requests `--version`, while `-v` continues to display the
+    /// short version.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .version("v0.1.24")
+    /// .long_version("v0.1.24 (2015-05-06)\nfeatures: yaml")
+    /// # .get_matches();
+    /// ```
+    pub fn long_version(mut self, v: &'v str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.long_version = Some(v);
+        self
+    }
+
+    /// Adds a piece of build metadata to be displayed along with the version when the user
+    /// requests detailed version information (i.e. `--version`, but not `-v`). Each piece of
+    /// metadata is displayed on its own line, in the order it was added, as `key: value` with all
+    /// values aligned.
+    ///
+    /// This is typically used with values supplied at compile time via the `env!()` or
+    /// `option_env!()` macros.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .version("v0.1.24")
+    /// .version_metadata("rustc", "1.0.0-beta.3")
+    /// # .get_matches();
+    /// ```
+    pub fn version_metadata(mut self, key: &'v str, value: &'v str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.version_meta.push((key, value));
+        self
+    }
+
+    /// Adds the commit hash the program was built from to the version information. This is a
+    /// convenience method for `.version_metadata("commit", hash)`
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .version("v0.1.24")
+    /// .git_hash("5f6c3a1")
+    /// # .get_matches();
+    /// ```
+    pub fn git_hash(self, hash: &'v str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.version_metadata("commit", hash)
+    }
+
+    /// Adds the target triple the program was built for to the version information. This is a
+    /// convenience method for `.version_metadata("target", triple)`
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .version("v0.1.24")
+    /// .target_triple("x86_64-unknown-linux-gnu")
+    /// # .get_matches();
+    /// ```
+    pub fn target_triple(self, triple: &'v str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.version_metadata("target", triple)
+    }
+
+    /// Adds the build profile (i.e. `debug` or `release`) to the version information. This is a
+    /// convenience method for `.version_metadata("profile", profile)`
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .version("v0.1.24")
+    /// .build_profile("release")
+    /// # .get_matches();
+    /// ```
+    pub fn build_profile(self, profile: &'v str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.version_metadata("profile", profile)
+    }
+
+    /// Sets a template used to lay out the version line, which is `{bin} {version}` by default.
+    /// The following placeholders are replaced with their respective values:
+    ///
+    /// * `{bin}` - the binary name
+    /// * `{version}` - the version number
+    /// * `{author}` - the author(s)
+    /// * `{hash}` - the commit hash set with `git_hash()`
+    /// * `{<key>}` - the value of any other build metadata set with `version_metadata()`, i.e.
+    /// `{target}` or `{profile}`
+    ///
+    /// **NOTE:** The detailed version set with `long_version()` is displayed as-is
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .version("v0.1.24")
+    /// .git_hash("5f6c3a1")
+    /// .version_template("{bin} {version} ({hash})")
+    /// # .get_matches();
+    /// ```
+    pub fn version_template(mut self, t: &'v str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.version_template = Some(t);
+        self
+    }
+
+    /// Writes the version line (as displayed for `-v`, using the template set with
+    /// `version_template()` if any) into a writer, i.e. to embed it into logs or diagnostics.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// use std::io;
+    ///
+    /// let app = App::new("myprog")
+    ///                .version("v0.1.24");
+    /// app.write_version(&mut io::stderr()).unwrap();
+    /// ```
+    pub fn write_version<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        // Print the binary name if existing, but replace all spaces with hyphens in case we're
+        // dealing with subcommands i.e. git mv is translated to git-mv
+        let bin_name = self.bin_name.clone().unwrap_or(self.name.clone()).replace(" ", "-");
+        let author = self.author_str();
+        let hash = self.version_meta.iter().filter(|&&(k, _)| k == "commit").map(|&(_, v)| v).next();
+        let mut values = vec![("bin", &bin_name[..]),
+                              ("version", self.version.unwrap_or("")),
+                              ("author", &author[..]),
+                              ("hash", hash.unwrap_or(""))];
+        values.extend(self.version_meta.iter().cloned());
+        writeln!(w, "{}", fill_template(self.version_template.unwrap_or("{bin} {version}"), &values[..]))
+    }
+
+    /// Writes the help information (as displayed for `-h`) into a writer instead of printing it
+    /// and exiting, i.e. for snapshot tests, GUIs, or to embed it into other output.
+    ///
+    /// **NOTE:** This requires `&mut self` since the automatic `-h`/`--help` and
+    /// `-v`/`--version` flags (and `help` subcommand) are added first, so the help is identical
+    /// to what's displayed at runtime
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// let mut app = App::new("myprog")
+    ///                    .about("Does awesome things");
+    /// let mut out = Vec::new();
+    /// app.write_help(&mut out).unwrap();
+    /// ```
+    pub fn write_help<W: io::Write>(&mut self, w: &mut W) -> io::Result<()> {
+        self.write_help_impl(w, false)
+    }
+
+    /// Writes the detailed help information (as displayed for `--help`, see `Arg::long_help()`)
+    /// into a writer instead of printing it and exiting (see `write_help()`).
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// use std::io;
+    ///
+    /// let mut app = App::new("myprog")
+    ///                    .about("Does awesome things");
+    /// app.write_long_help(&mut io::stdout()).unwrap();
+    /// ```
+    pub fn write_long_help<W: io::Write>(&mut self, w: &mut W) -> io::Result<()> {
+        self.write_help_impl(w, true)
+    }
+
+    // Adds the automatic help and version arguments before rendering the help, discarding any
+    // cached sections since arguments may have been added in the meantime
+    fn write_help_impl<W: io::Write>(&mut self, w: &mut W, long: bool) -> io::Result<()> {
+        self.create_help_and_version();
+        self.help_cache.borrow_mut().clear();
+        write!(w, "{}", self.create_help(long))
+    }
+
+    /// Sets a custom usage string to over-ride the auto-generated usage string. Will be
+    /// displayed to the user when errors are found in argument parsing, or when you call
+    /// `ArgMatches::usage()`
+    /// 
+    /// *NOTE:* You do not need to specify the "USAGE: \n\t" portion, as that will 
+    /// still be applied by `clap`, you only need to specify the portion starting
+    /// with the binary name. 
+    /// 
+    /// *NOTE:* This will not replace the entire help message, *only* the portion
+    /// showing the usage.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let app = App::new("myprog")
+    /// .usage("myapp [-clDas] <some_file>")
+    /// # .get_matches();
+    /// ```
+    pub fn usage(mut self, u: &'u str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.usage_str = Some(u);
+        self
+    }
+
+    /// Sets how the usage string of this subcommand relates to the custom usage string of its
+    /// parent, if the parent has one (see `ParentUsage`). By default subcommands ignore the
+    /// parent's custom usage string and generate their own. A custom usage string set on the
+    /// subcommand itself always takes precedence.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, ParentUsage, SubCommand};
+    /// # let app = App::new("myprog")
+    /// .usage("myapp [-clDas] <some_file>")
+    /// .subcommand(SubCommand::new("test")
+    ///                 .parent_usage(ParentUsage::Prefix))
+    /// # .get_matches();
+    /// ```
+    pub fn parent_usage(mut self, pu: ParentUsage) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.parent_usage = pu;
+        self
+    }
+
+    /// Hides this subcommand from the SUBCOMMANDS section of its parent's help information
+    /// (including the output of the auto-generated `help` subcommand). The subcommand can still
+    /// be used at runtime as usual. This is useful for deprecated or internal subcommands.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, SubCommand};
+    /// # let app = App::new("myprog")
+    /// .subcommand(SubCommand::new("debug-dump")
+    ///                 .hidden(true))
+    /// # .get_matches();
+    /// ```
+    pub fn hidden(mut self, h: bool) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.hidden = h;
+        self
+    }
+
+    /// Uses the version of this `App` (as well as the detailed version set with `long_version()`,
+    /// the build metadata, and the version template) for all subcommands, recursively, which
+    /// don't set their own. Otherwise subcommands display an empty version unless each one sets
+    /// it.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, SubCommand};
+    /// # let app = App::new("myprog")
+    /// .version("v0.1.24")
+    /// .global_version(true)
+    /// .subcommand(SubCommand::new("test"))
+    /// # .get_matches();
+    /// ```
+    pub fn global_version(mut self, g: bool) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.global_version = g;
+        self
+    }
+
+    /// Sets a function which is called with the time each phase of parsing took (see
+    /// `timings::Phase`), which is useful to detect regressions in startup latency. The function
+    /// is also used for all subcommands.
+    ///
+    /// **NOTE:** This requires the `timings` cargo feature
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # use clap::App;
+    /// # use clap::timings::Phase;
+    /// # use std::time::Duration;
+    /// fn report(phase: Phase, took: Duration) {
+    ///     println!("{:?} took {:?}", phase, took);
+    /// }
+    ///
+    /// # let app = App::new("myprog")
+    /// .timing_hook(report)
+    /// # .get_matches();
+    /// ```
+    #[cfg(feature = "timings")]
+    pub fn timing_hook(mut self, hook: TimingHook) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.timing_hook = Some(hook);
+        self
+    }
+
+    /// Treats negative numbers (i.e. `-5` or `-0.3`) as values of positional arguments and of
+    /// options with `multiple_values`, instead of attempting to parse them as clusters of short
+    /// flags. Unlike `Arg::allow_hyphen_values()` other values starting with a `-` are still
+    /// parsed as flags and options, and a number which is a valid short (i.e. `-1` with a flag
+    /// `-1`) continues to be parsed as such.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let app = App::new("myprog")
+    /// .allow_negative_numbers(true)
+    /// .arg(Arg::with_name("offset").index(1))
+    /// # .get_matches();
+    /// ```
+    pub fn allow_negative_numbers(mut self, allow: bool) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.allow_neg_nums = allow;
+        self
+    }
+
+    /// Captures all remaining arguments verbatim once the last positional argument (which must
+    /// accept `multiple` values) receives its first value, including those which look like flags,
+    /// options, or subcommands. This is meant for wrappers, i.e. `myprog run <cmd> <args>...`
+    /// where the arguments belong to the wrapped command, without requiring the user to type
+    /// `--` first.
+    ///
+    /// **NOTE:** With `help_overrides_errors()` a `-h` or `--help` meant for the wrapped command
+    /// still displays the help information of this application
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let app = App::new("myprog")
+    /// .trailing_var_arg(true)
+    /// .arg(Arg::with_name("cmd").index(1).multiple(true).required(true))
+    /// # .get_matches();
+    /// ```
+    pub fn trailing_var_arg(mut self, t: bool) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.trailing_var_arg = t;
+        self
+    }
+
+    /// Treats all arguments after `--` as positional arguments, even those matching the name of
+    /// a subcommand. By default a subcommand may still be used after `--`. This resolves the
+    /// ambiguity when a value (such as a file name) collides with the name of a subcommand, i.e.
+    /// `myprog -- test` would supply `test` to a positional argument instead of running the
+    /// `test` subcommand.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, SubCommand};
+    /// # let app = App::new("myprog")
+    /// .positional_after_separator(true)
+    /// .arg(Arg::with_name("file").index(1))
+    /// .subcommand(SubCommand::new("test"))
+    /// # .get_matches();
+    /// ```
+    pub fn positional_after_separator(mut self, p: bool) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.pos_after_sep = p;
+        self
+    }
+
+    /// Allows using subcommands which aren't defined, such as those implemented by separate
+    /// programs (i.e. `git-foo` for `git foo`). If the first value isn't a known subcommand, and
+    /// there are no positional arguments which could accept it, it's used as the name of an
+    /// external subcommand instead of being an error. All of the arguments after it are
+    /// captured as-is, without being parsed, and can be retrieved from the external
+    /// subcommand's matches with `values_of("")`.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// // Assume the program was run as "myprog frobnicate --x"
+    /// let matches = App::new("myprog")
+    ///                   .allow_external_subcommands(true)
+    ///                   .get_matches();
+    /// match matches.subcommand() {
+    ///     (external, Some(ext_m)) => {
+    ///         let ext_args = ext_m.values_of("").unwrap().collect::<Vec<_>>();
+    ///         assert_eq!(external, "frobnicate");
+    ///         assert_eq!(ext_args, ["--x"]);
+    ///     },
+    ///     _ => {},
+    /// }
+    /// ```
+    pub fn allow_external_subcommands(mut self, allow: bool) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.allow_external_subcmds = allow;
+        self
+    }
+
+    /// Allows the arguments captured for an external subcommand (see
+    /// `allow_external_subcommands()`) to contain invalid UTF-8, even when using `get_matches()`
+    /// which otherwise reports such arguments as an error. This is useful when forwarding command
+    /// lines, which frequently contain paths that aren't valid UTF-8, to another program. The
+    /// captured arguments can be retrieved as-is with `values_of_os("")`.
+    ///
+    /// **NOTE:** The name of the external subcommand itself must still be valid UTF-8
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// use std::process::Command;
+    ///
+    /// let matches = App::new("myprog")
+    ///                   .allow_external_subcommands(true)
+    ///                   .allow_invalid_utf8_for_external_subcommands(true)
+    ///                   .get_matches();
+    /// if let (external, Some(ext_m)) = matches.subcommand() {
+    ///     Command::new(format!("myprog-{}", external))
+    ///         .args(&ext_m.values_of_os("").unwrap().collect::<Vec<_>>())
+    ///         .status()
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn allow_invalid_utf8_for_external_subcommands(mut self, allow: bool) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.allow_invalid_utf8_ext = allow;
+        self
+    }
+
+    /// Displays the help (or version) information if `-h` or `--help` (or `-v` or `--version`)
+    /// appears anywhere in the arguments, even if other arguments contain errors. By default the
+    /// arguments are processed in order, so an error such as an invalid argument or a conflict
+    /// which appears *before* `--help` is reported instead of the help information (missing
+    /// required arguments never prevent the help information from being displayed). This
+    /// setting allows scripts to rely on `myprog --bad-flag --help` displaying the help
+    /// information.
+    ///
+    /// **NOTE:** Arguments after `--`, or after the name of a subcommand, aren't checked (the
+    /// subcommand uses its own setting)
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .help_overrides_errors(true)
+    /// # .get_matches();
+    /// ```
+    pub fn help_overrides_errors(mut self, o: bool) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.help_overrides_errors = o;
+        self
+    }
+
+    /// Allows subcommands to be used by any prefix of their name, so long as it's unambiguous,
+    /// i.e. `myprog tes` runs the `test` subcommand if no other subcommand starts with `tes`. If
+    /// multiple subcommands start with the prefix, an error listing them is displayed. Hidden
+    /// subcommands can only be used by their full name.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, SubCommand};
+    /// # let app = App::new("myprog")
+    /// .infer_subcommands(true)
+    /// .subcommand(SubCommand::new("test"))
+    /// # .get_matches();
+    /// ```
+    pub fn infer_subcommands(mut self, infer: bool) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.infer_subcmds = infer && !self.strict;
+        self
+    }
+
+    /// Allows long arguments to be abbreviated by any prefix of their name (or alias), so long as
+    /// it's unambiguous (i.e. `--verb` for `--verbose`, but not `--ver` when there is also
+    /// `--version`). If multiple long arguments start with the prefix, an error listing them is
+    /// displayed.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .infer_long_args(true)
+    /// .arg_from_usage("--verbose 'Prints more information'")
+    /// # .get_matches();
+    /// ```
+    pub fn infer_long_args(mut self, infer: bool) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.infer_long_args = infer && !self.strict;
+        self
+    }
+
+    /// Only accepts the exact spellings of arguments and subcommands, disabling all fuzzy
+    /// behaviors at once: subcommand and long argument inference, as well as the aliases of
+    /// arguments (which are also no longer displayed in the help information). This applies to
+    /// all subcommands, recursively, regardless of the order the settings, arguments, and
+    /// subcommands are added in. This is meant for security sensitive programs and wrappers,
+    /// where an unexpected spelling being accepted could be a problem.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let app = App::new("myprog")
+    /// .strict(true)
+    /// .arg(Arg::with_name("output").long("output").alias("out").takes_value(true))
+    /// # .get_matches();
+    /// ```
+    pub fn strict(mut self, s: bool) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.set_strict(s);
+        self
+    }
+
+    // Disables (or enables again) the fuzzy behaviors of this app and its subcommands,
+    // recursively. The aliases are kept, so they're accepted again once strict mode is turned off
+    fn set_strict(&mut self, s: bool) {
+        self.strict = s;
+        if s {
+            self.infer_subcmds = false;
+            self.infer_long_args = false;
+        }
+        for f in self.flags.values_mut() {
+            f.strict = s;
+        }
+        for o in self.opts.values_mut() {
+            o.strict = s;
+        }
+        for sc in self.subcommands.values_mut() {
+            sc.set_strict(s);
+        }
+    }
+
+    /// Sets a function which receives the error messages (along with the usage statement) and
+    /// warnings (i.e. about deprecated arguments) instead of them being written to stderr, i.e.
+    /// to capture them in tests or write them to a log. The program still exits after an error as
+    /// usual. This setting also applies to all
+    /// subcommands which don't set their own.
+    ///
+    /// **NOTE:** The help and version information are always written to stdout
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// fn log_error(msg: &str) {
+    ///     // ...
+    /// }
+    ///
+    /// # let app = App::new("myprog")
+    /// .error_output(log_error)
+    /// # .get_matches();
+    /// ```
+    pub fn error_output(mut self, f: fn(&str)) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.error_output = Some(f);
+        self
+    }
+
+    /// Sets a function which is called right before the arguments for this `App` (or
+    /// `SubCommand`) are parsed. This is useful for cross-cutting concerns such as telemetry,
+    /// or setting up the environment, without wrapping each call to `get_matches()`.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// fn start_timer() {
+    ///     // ...
+    /// }
+    ///
+    /// # let app = App::new("myprog")
+    /// .before_parse(start_timer)
+    /// # .get_matches();
+    /// ```
+    pub fn before_parse(mut self, f: fn()) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.before_parse = Some(f);
+        self
+    }
+
+    /// Sets a function which is called with the matches once the arguments for this `App` (or
+    /// `SubCommand`) have been successfully parsed and validated, including those of any
+    /// subcommand used. This is useful for cross-cutting concerns such as telemetry, or loading
+    /// configuration, without wrapping each call to `get_matches()`.
+    ///
+    /// **NOTE:** It's not called if parsing fails, or the help or version information is
+    /// displayed, since the program exits.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, ArgMatches};
+    /// fn load_config(matches: &ArgMatches) {
+    ///     let path = matches.value_of("config").unwrap_or("myprog.toml");
+    ///     // ...
+    /// }
+    ///
+    /// # let app = App::new("myprog")
+    /// .arg_from_usage("-c --config=[config] 'Sets a custom config file'")
+    /// .after_parse(load_config)
+    /// # .get_matches();
+    /// ```
+    pub fn after_parse(mut self, f: fn(&ArgMatches)) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.after_parse = Some(f);
+        self
+    }
+
+    /// Prints the help information and exits when the program (or subcommand) is run without any
+    /// arguments, instead of proceeding with no matches.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .arg_required_else_help(true)
+    /// # .get_matches();
+    /// ```
+    pub fn arg_required_else_help(mut self, r: bool) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.arg_required_else_help = r;
+        self
+    }
+
+    /// Suppresses the usage statement and "For more information try --help" hint which are
+    /// normally displayed after an error message, so that only the error message itself is
+    /// displayed. The program still exits with the same status code. This setting also applies to
+    /// all subcommands.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .dont_print_full_usage_on_error(true)
+    /// # .get_matches();
+    /// ```
+    pub fn dont_print_full_usage_on_error(mut self, quiet: bool) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.quiet_errors = quiet;
+        self
+    }
+
+    /// Enables a behavioral setting for this `App` (see `AppSettings` for the available
+    /// settings). Settings which also have their own method, i.e. `AppSettings::TrailingVarArg`
+    /// and `trailing_var_arg()`, are equivalent to it.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, AppSettings};
+    /// # let app = App::new("myprog")
+    /// .setting(AppSettings::SubcommandRequired)
+    /// # .get_matches();
+    /// ```
+    pub fn setting(mut self, s: AppSettings) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.set(s, true);
+        self
+    }
+
+    /// Enables multiple behavioral settings for this `App` (see `setting()`)
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, AppSettings};
+    /// # let app = App::new("myprog")
+    /// .settings(&[AppSettings::SubcommandRequired, AppSettings::InferSubcommands])
+    /// # .get_matches();
+    /// ```
+    pub fn settings(mut self, ss: &[AppSettings]) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        for s in ss {
+            self.set(*s, true);
+        }
+        self
+    }
+
+    /// Disables a behavioral setting for this `App` (see `setting()`), i.e. one which was
+    /// enabled by a global setting of its parent
+    ///
+    /// **NOTE:** Global settings of a parent are applied when parsing reaches the subcommand, so
+    /// they take precedence over this
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, AppSettings};
+    /// # let app = App::new("myprog")
+    /// .unset_setting(AppSettings::InferSubcommands)
+    /// # .get_matches();
+    /// ```
+    pub fn unset_setting(mut self, s: AppSettings) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.set(s, false);
+        self
+    }
+
+    /// Enables a behavioral setting for this `App` and all of its subcommands, including those
+    /// which are added afterwards (see `setting()`)
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, AppSettings};
+    /// # let app = App::new("myprog")
+    /// .global_setting(AppSettings::AllowNegativeNumbers)
+    /// # .get_matches();
+    /// ```
+    pub fn global_setting(mut self, s: AppSettings) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.set(s, true);
+        self.g_settings.push(s);
+        self
+    }
+
+    // Turns a setting on or off, by way of the field (or method) backing it
+    fn set(&mut self, s: AppSettings, on: bool) {
+        match s {
+            AppSettings::Hidden                                 => self.hidden = on,
+            AppSettings::GlobalVersion                          => self.global_version = on,
+            AppSettings::AllowNegativeNumbers                   => self.allow_neg_nums = on,
+            AppSettings::TrailingVarArg                         => self.trailing_var_arg = on,
+            AppSettings::PositionalAfterSeparator               => self.pos_after_sep = on,
+            AppSettings::AllowExternalSubcommands               => self.allow_external_subcmds = on,
+            AppSettings::AllowInvalidUtf8ForExternalSubcommands => self.allow_invalid_utf8_ext = on,
+            AppSettings::HelpOverridesErrors                    => self.help_overrides_errors = on,
+            // Inference stays disabled in strict mode, as with infer_subcommands() and
+            // infer_long_args()
+            AppSettings::InferSubcommands                       => self.infer_subcmds = on && !self.strict,
+            AppSettings::InferLongArgs                          => self.infer_long_args = on && !self.strict,
+            AppSettings::Strict                                 => self.set_strict(on),
+            AppSettings::ArgRequiredElseHelp                    => self.arg_required_else_help = on,
+            AppSettings::DontPrintFullUsageOnError              => self.quiet_errors = on,
+            AppSettings::DescribeRelations                      => self.help_relations = on,
+            AppSettings::UnifiedHelpMessage                     => self.unified_help = on,
+            AppSettings::SubcommandRequired                     => self.subcmd_required = on,
+            // When enabled again, the flags are only generated if no argument took them over
+            AppSettings::DisableHelpFlag                        => {
+                self.disable_help = on;
+                self.needs_long_help = !on && !self.long_list.contains("help");
+                self.needs_short_help = !on && self.help_short().map_or(false, |s| !self.short_list.contains(&s));
+            },
+            AppSettings::DisableVersionFlag                     => {
+                self.disable_version = on;
+                self.needs_long_version = !on && !self.long_list.contains("version");
+                self.needs_short_version = !on && self.version_short().map_or(false, |s| !self.short_list.contains(&s));
+            },
+            AppSettings::DisableHelpSubcommand                  => {
+                self.needs_subcmd_help = !on && !self.subcommands.contains_key("help");
+            }
+        }
+    }
+
+    /// Sets the status code the program exits with when an error is reported, which is `1` by
+    /// default (some conventions use `2` for usage errors, or `64` for `EX_USAGE`). This setting
+    /// also applies to all subcommands which don't set their own.
+    ///
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use clap::App;
     /// # let app = App::new("myprog")
-    /// .after_help("Does really amazing things to great people")
+    /// .exit_code_on_error(64)
     /// # .get_matches();
     /// ```
-    pub fn after_help(mut self, h: &'h str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
-        self.more_help = Some(h);
+    pub fn exit_code_on_error(mut self, code: u8) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.error_code = Some(code);
         self
     }
 
-    /// Sets a string of the version number to be displayed when displaying version or help
-    /// information.
+    /// Sets the status code the program exits with after displaying the help information (i.e.
+    /// for `-h`, `--help`, or the `help` subcommand), which is `0` by default. This setting also
+    /// applies to all subcommands which don't set their own.
+    ///
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use clap::{App, Arg};
+    /// # use clap::App;
     /// # let app = App::new("myprog")
-    /// .version("v0.1.24")
+    /// .exit_code_on_help(2)
     /// # .get_matches();
     /// ```
-    pub fn version(mut self, v: &'v str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar>  {
-        self.version = Some(v);
+    pub fn exit_code_on_help(mut self, code: u8) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.help_code = Some(code);
         self
     }
-    
-    /// Sets a custom usage string to over-ride the auto-generated usage string. Will be
-    /// displayed to the user when errors are found in argument parsing, or when you call
-    /// `ArgMatches::usage()`
-    /// 
-    /// *NOTE:* You do not need to specify the "USAGE: \n\t" portion, as that will 
-    /// still be applied by `clap`, you only need to specify the portion starting
-    /// with the binary name. 
-    /// 
-    /// *NOTE:* This will not replace the entire help message, *only* the portion
-    /// showing the usage.
+
+    /// Sets the status code the program exits with after displaying the version (i.e. for `-v`
+    /// or `--version`), which is `0` by default. This setting also applies to all subcommands
+    /// which don't set their own.
     ///
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use clap::{App, Arg};
+    /// # use clap::App;
     /// # let app = App::new("myprog")
-    /// .usage("myapp [-clDas] <some_file>")
+    /// .exit_code_on_version(2)
     /// # .get_matches();
     /// ```
-    pub fn usage(mut self, u: &'u str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
-        self.usage_str = Some(u);
+    pub fn exit_code_on_version(mut self, code: u8) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        self.version_code = Some(code);
         self
     }
 
@@ -237,6 +1686,9 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
         } else {
             self.arg_list.insert(a.name);
         }
+        if let Some(ref except) = a.conflicts_except {
+            self.conflicts_except.push((a.name, except.clone()));
+        }
         if let Some(grp) = a.group {
             let ag = self.groups.entry(grp).or_insert(ArgGroup::with_name(grp));
             ag.args.insert(a.name);
@@ -251,9 +1703,9 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
             } else {
                 self.short_list.insert(s);
             }
-            if s == 'h' {
+            if Some(s) == self.help_short() {
                 self.needs_short_help = false;
-            } else if s == 'v' {
+            } else if Some(s) == self.version_short() {
                 self.needs_short_version = false;
             }
         }
@@ -269,15 +1721,33 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                 self.needs_long_version = false;
             }
         }
-        if a.required {
+        if let Some(ref aliases) = a.aliases {
+            if a.long.is_none() {
+                panic!("Argument \"{}\" has aliases, yet no long() was supplied", a.name);
+            }
+            for &(l, _) in aliases.iter() {
+                if self.long_list.contains(l) {
+                    panic!("Argument long must be unique\n\n\t--{} is already in use", l);
+                } else {
+                    self.long_list.insert(l);
+                }
+            }
+        }
+        if a.required || a.r_unless.is_some() {
             self.required.insert(a.name);
         }
-        if a.index.is_some() || (a.short.is_none() && a.long.is_none()) {
+        if a.global {
+            self.global_args.push(a.clone());
+        }
+        if a.index.is_some() || (a.short.is_none() && a.long.is_none() && a.env.is_none()) {
             let i = if a.index.is_none() {(self.positionals_idx.len() + 1) as u8 } else { a.index.unwrap() };
 
             if a.short.is_some() || a.long.is_some() {
                 panic!("Argument \"{}\" has conflicting requirements, both index() and short(), or long(), were supplied", a.name);
             }
+            if a.global {
+                panic!("Argument \"{}\" is global, yet is a positional argument\n\n\tOnly flags and options may be global", a.name);
+            }
 
             if self.positionals_idx.contains_key(&i) {
                 panic!("Argument \"{}\" has the same index as another positional argument\n\n\tPerhaps try .multiple(true) to allow one positional argument to take multiple values", a.name);
@@ -291,12 +1761,33 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
             let mut pb = PosBuilder {
                 name: a.name,
                 index: i,
+                val_name: a.val_names.as_ref().map(|n| n[0]),
+                value_hint: a.value_hint,
+                candidates: a.candidates,
                 required: a.required,
-                multiple: a.multiple,
+                r_unless: a.r_unless.clone(),
+                r_unless_all: a.r_unless_all,
+                r_ifs: a.r_ifs.clone(),
+                requires_ifs: a.requires_ifs.clone(),
+                multiple: a.multiple || a.multiple_vals,
+                allow_hyphen_vals: a.allow_hyphen_vals,
+                last: a.last,
                 blacklist: None,
+                overrides: a.overrides.clone(),
                 requires: None,
+                groups: self.groups_of(a.name),
                 possible_vals: None,
+                default_val: a.default_val,
+                default_val_ifs: a.default_val_ifs.clone(),
+                min_vals: a.min_vals,
+                max_vals: a.max_vals,
                 help: a.help,
+                long_help: a.long_help,
+                error_message: a.error_message,
+                disp_name: a.disp_name,
+                deprecated: a.deprecated,
+                hidden: a.hidden,
+                help_heading: a.help_heading,
             };
             // Check if there is anything in the blacklist (mutually excludes list) and add any values
             if let Some(ref bl) = a.blacklist {
@@ -321,8 +1812,9 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                 pb.possible_vals = Some(phs);
             }
             self.positionals_idx.insert(i, pb);
-        } else if a.takes_value {
-            if a.short.is_none() && a.long.is_none() {
+        } else if a.takes_value || a.env.is_some() {
+            // Arguments without a short or long are populated only from the environment
+            if a.short.is_none() && a.long.is_none() && a.env.is_none() {
                 panic!("Argument \"{}\" has take_value(true), yet neither a short() or long() were supplied", a.name);
             }
             // No need to check for .index() as that is handled above
@@ -330,12 +1822,41 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                 name: a.name,
                 short: a.short,
                 long: a.long,
+                env: a.env,
+                aliases: a.aliases.clone(),
+                strict: self.strict,
                 multiple: a.multiple,
                 blacklist: None,
+                overrides: a.overrides.clone(),
                 help: a.help,
+                long_help: a.long_help,
                 possible_vals: None,
+                default_val: a.default_val,
+                default_val_ifs: a.default_val_ifs.clone(),
+                default_missing_val: a.default_missing_val,
+                multiple_vals: a.multiple_vals,
+                allow_hyphen_vals: a.allow_hyphen_vals,
+                sep_as_val: a.sep_as_val,
+                val_delim: if a.req_delim || a.use_delim.unwrap_or(a.multiple || a.multiple_vals) { Some(a.val_delim.unwrap_or(',')) } else { None },
+                req_delim: a.req_delim,
+                num_vals: a.num_vals.or(a.val_names.as_ref().and_then(|n| if n.len() > 1 { Some(n.len() as u8) } else { None })),
+                val_names: a.val_names.clone(),
+                value_hint: a.value_hint,
+                candidates: a.candidates,
+                min_vals: a.min_vals,
+                max_vals: a.max_vals,
                 requires: None,
+                groups: self.groups_of(a.name),
                 required: a.required,
+                r_unless: a.r_unless.clone(),
+                r_unless_all: a.r_unless_all,
+                r_ifs: a.r_ifs.clone(),
+                requires_ifs: a.requires_ifs.clone(),
+                error_message: a.error_message,
+                disp_name: a.disp_name,
+                deprecated: a.deprecated,
+                hidden: a.hidden,
+                help_heading: a.help_heading,
             };
             // Check if there is anything in the blacklist (mutually excludes list) and add any values
             if let Some(ref bl) = a.blacklist {
@@ -364,22 +1885,41 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                 // Could be a posistional constructed from usage string
 
             }
-            if a.required {
+            if a.required || a.r_unless.is_some() || a.r_ifs.is_some() {
                 panic!("Argument \"{}\" cannot be required(true) because it has no index() or takes_value(true)", a.name);
             }
+            if a.requires_ifs.is_some() {
+                panic!("Argument \"{}\" cannot require arguments depending on its value because it doesn't have takes_value(true) set", a.name);
+            }
             if a.possible_vals.is_some() {
                 panic!("Argument \"{}\" cannot have a specific value set because it doesn't have takes_value(true) set", a.name);
             }
+            if a.default_val.is_some() || a.default_val_ifs.is_some() || a.default_missing_val.is_some() {
+                panic!("Argument \"{}\" cannot have a default value because it doesn't have takes_value(true) set", a.name);
+            }
+            if a.multiple_vals {
+                panic!("Argument \"{}\" cannot accept multiple values because it doesn't have takes_value(true) set", a.name);
+            }
             // No need to check for index() or takes_value() as that is handled above
 
             let mut fb = FlagBuilder {
                 name: a.name,
                 short: a.short,
                 long: a.long,
+                aliases: a.aliases.clone(),
+                strict: self.strict,
                 help: a.help,
+                long_help: a.long_help,
                 blacklist: None,
+                overrides: a.overrides.clone(),
                 multiple: a.multiple,
                 requires: None,
+                groups: self.groups_of(a.name),
+                error_message: a.error_message,
+                disp_name: a.disp_name,
+                deprecated: a.deprecated,
+                hidden: a.hidden,
+                help_heading: a.help_heading,
             };
             // Check if there is anything in the blacklist (mutually excludes list) and add any values
             if let Some(ref bl) = a.blacklist {
@@ -446,6 +1986,8 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
     /// `Arg::from_usage()` supports. But here the benefit is pretty strong, as the readability is
     /// greatly enhanced, especially if you don't need any of the more advanced configuration options.
     ///
+    /// Blank lines, and lines starting with a `#` (comments), are ignored.
+    ///
     ///
     /// # Example
     ///
@@ -455,12 +1997,51 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
     /// .args_from_usage(
     ///    "-c --conf=[config] 'Sets a configuration file to use'
     ///    [debug]... -d 'Sets the debugging level'
+    ///
+    ///    # positional arguments
     ///    <input> 'The input file to use'")
     /// # .get_matches();
     /// ```
     pub fn args_from_usage(mut self, usage: &'ar str) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
-        for l in usage.lines() {
-            self = self.arg(Arg::from_usage(l.trim()));
+        for l in usage.lines().map(|l| l.trim()).filter(|l| !l.is_empty() && !l.starts_with("#")) {
+            self = self.arg(Arg::from_usage(l));
+        }
+        self
+    }
+
+    /// Customizes one of the automatically generated arguments, `help` or `version`, i.e. to
+    /// translate its help message, change its short version, or hide it. The function is passed
+    /// the argument as it's currently set up, and returns the modified argument.
+    ///
+    /// **NOTE:** Only the short, help message (and long help message), `hidden()`, and
+    /// `help_heading()` of the argument are used, the long version always stays `--help` (or
+    /// `--version`). Setting a short which is already in use by another argument removes the
+    /// short from the automatic argument.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let app = App::new("myprog")
+    /// .mut_arg("help", |a| a.help("Skriver ut hjälp"))
+    /// .mut_arg("version", |a| a.short("V"))
+    /// # .get_matches();
+    /// ```
+    pub fn mut_arg<F>(mut self, name: &str, f: F) -> App<'a, 'v, 'ab, 'u, 'h, 'ar>
+                      where F: FnOnce(Arg<'ar, 'ar, 'ar, 'ar, 'ar, 'ar>) -> Arg<'ar, 'ar, 'ar, 'ar, 'ar, 'ar> {
+        match name {
+            "help"    => {
+                let a = self.help_arg.take().unwrap_or(Arg::with_name("help").short("h").long("help").help("Prints help information"));
+                self.help_arg = Some(f(a));
+                self.needs_short_help = !self.disable_help && self.help_short().map_or(false, |s| !self.short_list.contains(&s));
+            },
+            "version" => {
+                let a = self.version_arg.take().unwrap_or(Arg::with_name("version").short("v").long("version").help("Prints version information"));
+                self.version_arg = Some(f(a));
+                self.needs_short_version = !self.disable_version && self.version_short().map_or(false, |s| !self.short_list.contains(&s));
+            },
+            _         => panic!("mut_arg() only customizes the automatic arguments \"help\" and \"version\", \"{}\" isn't one of them", name)
         }
         self
     }
@@ -497,7 +2078,7 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
     ///                     .add_all(vec!["ver", "major", "minor","patch"])
     ///                     .required(true))
     /// # .get_matches();
-    pub fn arg_group(mut self, group: ArgGroup<'ar, 'ar>) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+    pub fn arg_group(mut self, mut group: ArgGroup<'ar, 'ar>) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
         if group.required {
             self.required.insert(group.name);
             if let Some(ref reqs) = group.requires {
@@ -511,22 +2092,41 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                 }
             }
         }
-        let mut found = false;
-        if let Some(ref mut grp) = self.groups.get_mut(group.name) {
-            for a in group.args.iter() {
-                grp.args.insert(a);
-            }
-            grp.requires = group.requires.clone();
-            grp.conflicts = group.conflicts.clone();
-            grp.required = group.required;
-            found = true;
-        }  
-        if !found {
-            self.groups.insert(group.name, group);
+        for a in group.args.iter() {
+            self.add_to_group(a, group.name);
         }
+        // The group may already exist because arguments named it with Arg::group(), in which
+        // case it only keeps those arguments, and takes everything else from this one
+        if let Some(grp) = self.groups.remove(group.name) {
+            group.args.extend(grp.args.into_iter());
+        }
+        self.groups.insert(group.name, group);
         self
     }
 
+    // The names of the groups the argument with the given name belongs to
+    fn groups_of(&self, name: &str) -> Vec<&'ar str> {
+        self.groups.values().filter(|g| g.args.contains(name)).map(|g| g.name).collect()
+    }
+
+    // Lets an argument which was already added know it belongs to a group, so membership is the
+    // same whether it was declared by the argument or the group
+    fn add_to_group(&mut self, name: &str, group: &'ar str) {
+        let groups = if let Some(f) = self.flags.get_mut(name) {
+            &mut f.groups
+        } else if let Some(o) = self.opts.get_mut(name) {
+            &mut o.groups
+        } else {
+            match self.positionals_idx.values_mut().filter(|p| p.name == name).next() {
+                Some(p) => &mut p.groups,
+                None    => return
+            }
+        };
+        if !groups.contains(&group) {
+            groups.push(group);
+        }
+    }
+
     /// Adds a ArgGroups to the application. ArgGroups are a family of related arguments. By 
     /// placing them in a logical group, you make easier requirement and exclusion rules. For 
     /// instance, you can make an ArgGroup required, this means that one (and *only* one) argument
@@ -582,8 +2182,11 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
     ///             // Additional subcommand configuration goes here, such as other arguments...
     /// # .get_matches();
     /// ```
-    pub fn subcommand(mut self, subcmd: App<'a, 'v, 'ab, 'u, 'h, 'ar>) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+    pub fn subcommand(mut self, mut subcmd: App<'a, 'v, 'ab, 'u, 'h, 'ar>) -> App<'a, 'v, 'ab, 'u, 'h, 'ar> {
         if subcmd.name == "help" { self.needs_subcmd_help = false; }
+        if self.strict {
+            subcmd.set_strict(true);
+        }
         self.subcommands.insert(subcmd.name.clone(), subcmd);
         self
     }
@@ -616,12 +2219,22 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
         let mut usage = String::with_capacity(75);
         usage.push_str("USAGE:\n");
         usage.push_str(tab);
+        usage.push_str(&self.create_usage_no_title()[..]);
+        usage.shrink_to_fit();
+        usage
+    }
+
+    // Creates the usage string without the leading "USAGE:" title, i.e. only the portion
+    // starting with the binary name
+    pub(crate) fn create_usage_no_title(&self) -> String {
+        let mut usage = String::with_capacity(75);
         if let Some(u) = self.usage_str {
             usage.push_str(u);
         } else {
-            let flags = !self.flags.is_empty();
-            let pos = !self.positionals_idx.is_empty();
-            let opts = !self.opts.is_empty();
+            let flags = !self.flags.is_empty() || !self.pending_auto_flags().is_empty();
+            let pos = self.positionals_idx.values().any(|p| !p.last);
+            let last = self.positionals_idx.values().filter(|p| p.last).next();
+            let opts = self.opts.values().any(|o| !o.env_only());
             let subcmds = !self.subcommands.is_empty();
             let groups = !self.groups.is_empty();
             let mut num_req_pos = 0;
@@ -639,7 +2252,7 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                 }
             }
 
-            let mut req_pos = self.positionals_idx.values().filter_map(|ref x| if x.required || matched_pos_reqs.contains(x.name) { 
+            let mut req_pos = self.positionals_idx.values().filter(|p| !p.last).filter_map(|ref x| if x.required || matched_pos_reqs.contains(x.name) { 
                 num_req_pos += 1;
                 Some(format!("{}", x))
             } else {
@@ -648,7 +2261,7 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                                                        .fold(String::with_capacity(50), |acc, ref name| acc + &format!("{} ", name)[..]);
             req_pos.shrink_to_fit();
             let mut num_req_opts = 0;
-            let mut req_opts = self.opts.values().filter_map(|x| if x.required  || self.matched_reqs.contains(x.name) {
+            let mut req_opts = self.opts.values().filter(|o| !o.env_only()).filter_map(|x| if x.required  || self.matched_reqs.contains(x.name) {
                 num_req_opts += 1;
                 Some(x)
             }else {
@@ -657,13 +2270,15 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                                                  .fold(String::with_capacity(50), |acc, ref o| acc + &format!("{} ",o));
             req_opts.shrink_to_fit();
 
-            usage.push_str(&self.bin_name.clone().unwrap_or(self.name.clone())[..]);
-            if flags {
+            usage.push_str(&self.usage_prefix.clone().unwrap_or(self.bin_name.clone().unwrap_or(self.name.clone()))[..]);
+            // The flags are listed among the options with unified_help_message()
+            let flag_opts = flags && self.unified_help;
+            if flags && !flag_opts {
                 usage.push_str(" [FLAGS]");
             }
-            if opts {
+            if opts || flag_opts {
                 write!(&mut usage," {}",
-                    if num_req_opts != self.opts.len() && !req_opts.is_empty() { 
+                    if (num_req_opts != self.opts.values().filter(|o| !o.env_only()).count() || flag_opts) && !req_opts.is_empty() { 
                         format!("[OPTIONS] {}", &req_opts[..])
                     } else if req_opts.is_empty() { 
                         "[OPTIONS]".to_owned()
@@ -673,7 +2288,7 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
             }
             if pos {
                 write!(&mut usage, " {}",
-                    if num_req_pos != self.positionals_idx.len() && !req_pos.is_empty() { 
+                    if num_req_pos != self.positionals_idx.values().filter(|p| !p.last).count() && !req_pos.is_empty() { 
                         format!("[POSITIONAL] {}", &req_pos[..])
                     } else if req_pos.is_empty() { 
                         "[POSITIONAL]".to_owned()
@@ -685,55 +2300,156 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                 let req_grps = self.groups.values()                                                         // Iterator<Item=ArgGroup>
                                           .filter_map(|g| if g.required {Some(g.args.clone())} else {None}) // Iterator<Item=HashSet<&str>>
                                           .map(|hs| hs.into_iter().collect::<Vec<_>>() )                    // Iterator<Item=Vec<&str>>
-                                          .fold(vec![], |acc, n| acc + &n[..])                              // Vec<&str>
+                                          .fold(vec![], |mut acc: Vec<&str>, n| { acc.extend_from_slice(&n[..]); acc })                              // Vec<&str>
                                           .iter()                                                           // Iterator<Item=&str>
-                                          .fold(String::new(), |acc, n| {
-                                                if let Some(ref o) = self.opts.get(n) {
-                                                    acc + &format!("{}|", o)[..]
-                                                } else if let Some(ref f) = self.flags.get(n) {
-                                                    acc + &format!("{}|", f)[..]
-                                                } else {
-                                                    acc + &format!("{}|", match self.positionals_idx.values().rev().filter_map(|ref p| if &p.name == n {Some(format!("{}", p))}else{None}).next(){
-                                                        Some(name) => name,
-                                                        None       => panic!(format!("Error parsing a required group which contains argument \"{}\"\n\n\tArgument couldn't be found. Check the arguments settings.", n)) 
-                                                    })[..]
-                                                }
-                                          });
+                                          .fold(String::new(), |acc, n| acc + &format!("{}|", self.group_arg_usage(n))[..]);
 
                 // There may be no required groups, so we check
                 if req_grps.len() > 0 {
                     write!(&mut usage, " [{}]", &req_grps[..req_grps.len() - 1]).unwrap_or_else(|e| self.report_error(format!("internal error: {}", e),false,true));
                 }
+
+                // Optional groups are only displayed when requested, sorted so the usage string
+                // doesn't depend on the order of the HashSet
+                let mut opt_grps = self.groups.values().filter(|g| !g.required && g.in_usage).collect::<Vec<_>>();
+                opt_grps.sort_by(|a, b| a.name.cmp(b.name));
+                for g in opt_grps {
+                    let mut args = g.args.iter().map(|n| self.group_arg_usage(n)).collect::<Vec<_>>();
+                    args.sort();
+                    write!(&mut usage, " [{}]", args.join("|")).unwrap_or_else(|e| self.report_error(format!("internal error: {}", e),false,true));
+                }
             }
             if subcmds {
-                usage.push_str(" [SUBCOMMANDS]");
+                usage.push_str(if self.subcmd_required { " <SUBCOMMAND>" } else { " [SUBCOMMANDS]" });
             }                
+            if let Some(p) = last {
+                let len = usage.trim_right().len();
+                usage.truncate(len);
+                let args = format!("-- <{}>{}", p.usage_name(), if p.multiple { "..." } else { "" });
+                if p.required || matched_pos_reqs.contains(p.name) {
+                    write!(&mut usage, " {}", args)
+                } else {
+                    write!(&mut usage, " [{}]", args)
+                }.unwrap_or_else(|e| self.report_error(format!("internal error: {}", e),false,true));
+            }
         }
 
         usage.shrink_to_fit();
         usage
     }
 
-    // Prints the usage statement to the user
-    fn print_usage(&self, more_info: bool) {
-        print!("{}",self.create_usage());
-        if more_info {
-            println!("\nFor more information try --help");
+    // The usage of a single argument of a group, as displayed in the usage string
+    fn group_arg_usage(&self, n: &str) -> String {
+        if let Some(ref o) = self.opts.get(n) {
+            format!("{}", o)
+        } else if let Some(ref f) = self.flags.get(n) {
+            format!("{}", f)
+        } else {
+            match self.positionals_idx.values().rev().filter_map(|ref p| if p.name == n {Some(format!("{}", p))}else{None}).next(){
+                Some(name) => name,
+                None       => panic!(format!("Error parsing a group which contains argument \"{}\"\n\n\tArgument couldn't be found. Check the arguments settings.", n)) 
+            }
         }
     }
 
-    // Prints the full help message to the user
-    fn print_help(&self) {
-        self.print_version(false);
-        let flags = !self.flags.is_empty();
-        let pos = !self.positionals_idx.is_empty();
-        let opts = !self.opts.is_empty();
-        let subcmds = !self.subcommands.is_empty();
+    // Prints the full help message to the user, using the detailed descriptions when `long` is set
+    // (i.e. --help as opposed to -h)
+    fn print_help(&self, long: bool) {
+        #[cfg(feature = "timings")]
+        let start = Instant::now();
+        let help = self.create_help(long);
+        #[cfg(feature = "timings")]
+        self.report_timing(Phase::HelpGeneration, start);
+        print!("{}", help);
+        self.exit(Exit::Help);
+    }
+
+    // Creates the full help message, using the custom help message or help template if one was
+    // provided
+    fn create_help(&self, long: bool) -> String {
+        if let Some(h) = self.help_str {
+            return if h.ends_with("\n") { h.to_owned() } else { format!("{}\n", h) };
+        }
+        let ArgsHelp { flags: flags_s, opts: opts_s, pos: pos_s, subcmds: subcmds_s, groups } = self.args_help(long);
+
+        // Arguments with a heading (or belonging to a group with one) are listed in its section instead
+        let flags = !flags_s.is_empty();
+        let pos = !pos_s.is_empty();
+        let opts = !opts_s.is_empty();
+        let subcmds = self.subcommands.values().any(|sc| !sc.hidden);
+
+        let about = if long { self.long_about.or(self.about) } else { self.about };
+        let before = if long { self.before_long_help.or(self.before_help) } else { self.before_help };
+        let after = if long { self.after_long_help.or(self.more_help) } else { self.more_help };
+
+        let mut all_args = String::new();
+        for &(used, title, ref s) in [(flags, "FLAGS:", &flags_s),
+                                      (opts, "OPTIONS:", &opts_s),
+                                      (pos, "POSITIONAL ARGUMENTS:", &pos_s)].iter() {
+            if !used { continue; }
+            all_args.push_str(&format!("\n{}\n{}", title, s)[..]);
+        }
+        for &(ref title, ref s) in groups.iter() {
+            all_args.push_str(&format!("\n{}\n{}", title, s)[..]);
+        }
+        if subcmds {
+            all_args.push_str(&format!("\nSUBCOMMANDS:\n{}", subcmds_s)[..]);
+        }
+
+        if let Some(t) = self.help_template {
+            let bin_name = self.bin_name.clone().unwrap_or(self.name.clone()).replace(" ", "-");
+            let mut help = fill_template(t, &[("bin", &bin_name[..]),
+                                              ("version", self.version.unwrap_or("")),
+                                              ("author", &self.author_str()[..]),
+                                              ("about", about.unwrap_or("")),
+                                              ("usage", &self.create_usage_no_title()[..]),
+                                              ("all-args", all_args.trim_matches('\n')),
+                                              ("flags", flags_s.trim_right()),
+                                              ("options", opts_s.trim_right()),
+                                              ("positionals", pos_s.trim_right()),
+                                              ("subcommands", subcmds_s.trim_right()),
+                                              ("before-help", before.unwrap_or("")),
+                                              ("after-help", after.unwrap_or(""))]);
+            help.push('\n');
+            return help;
+        }
+
+        let mut help = match before {
+            Some(b) => format!("{}\n\n", b),
+            None    => String::new()
+        };
+        help.push_str(&format!("{} {}\n", &self.bin_name.clone().unwrap_or(self.name.clone())[..].replace(" ", "-"), self.version.unwrap_or(""))[..]);
+        if self.author.is_some() {
+            help.push_str(&format!("{}\n", self.author_str())[..]);
+        }
+        if let Some(about) = about {
+            help.push_str(&format!("{}\n", about)[..]);
+        }
+        help.push_str("\n");
+        help.push_str(&self.create_usage()[..]);
+        if !all_args.is_empty() {
+            help.push_str("\n");
+        }
+        help.push_str(&all_args[..]);
+
+        if let Some(h) = after {
+            help.push_str(&format!("\n{}\n", h)[..]);
+        }
+        help
+    }
 
+    // Renders the sections listing the arguments and subcommands, which is most of the work of
+    // creating the help message. These can't change once parsing has begun (which discards
+    // anything cached before), so they're cached for repeated help requests (i.e. when embedded
+    // in a long running interactive host)
+    fn args_help(&self, long: bool) -> ArgsHelp {
+        if let Some(h) = self.help_cache.borrow().get(&long) {
+            return h.clone();
+        }
         let mut longest_flag = 0;
         for fl in self.flags
             .values()
-            .filter(|ref f| f.long.is_some())
+            .filter(|ref f| f.long.is_some() && !f.hidden)
             // 2='--'
             .map(|ref a| a.long.unwrap().len() + 2) {
             if fl > longest_flag { longest_flag = fl; }
@@ -741,53 +2457,74 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
         let mut longest_opt= 0;
         for ol in self.opts
             .values()
-            .filter(|ref o| o.long.is_some())
-            // 3='...'
-            // 5='-- <>'
-            .map(|ref a| if a.multiple { 3 } else { 0 } + a.long.unwrap().len() + 5 + a.name.len() ) {
+            .filter(|ref o| o.long.is_some() && !o.hidden)
+            // 3='-- '
+            .map(|ref a| a.long.unwrap().len() + 3 + a.vals_usage().len() ) {
             if ol > longest_opt {longest_opt = ol;}
         }
         if longest_opt == 0 {
             for ol in self.opts
                 .values()
-                .filter(|ref o| o.short.is_some())
-                // 3='...'
-                // 4='- <>'
-                .map(|ref a| if a.multiple { 3 } else { 0 } + a.name.len() + 4) {
+                .filter(|ref o| o.short.is_some() && !o.hidden)
+                // 2='- '
+                .map(|ref a| a.vals_usage().len() + 2) {
                 if ol > longest_opt {longest_opt = ol;}
             }
         }
         let mut longest_pos = 0;
         for pl in self.positionals_idx
             .values()
-            .map(|ref f| if f.multiple { f.name.len() + 3 } else { f.name.len() } ) {
+            .filter(|ref p| !p.hidden)
+            .map(|ref f| if f.multiple { f.usage_name().len() + 3 } else { f.usage_name().len() } ) {
             if pl > longest_pos {longest_pos = pl;}
         }
         let mut longest_sc = 0;
         for scl in self.subcommands
             .values()
+            .filter(|ref f| !f.hidden)
             .map(|ref f| f.name.len() ) {
             if scl > longest_sc {longest_sc = scl;}
         }
-        
-        if let Some(author) = self.author {
-            println!("{}", author);
-        }
-        if let Some(about) = self.about {
-            println!("{}", about);
-        }
-        println!("");
-        self.print_usage(false);
-        if flags || opts || pos || subcmds {
-            println!("");
-        }
 
         let tab = "    ";
-        if flags {
-            println!("");
-            println!("FLAGS:");
-            for v in self.flags.values() {
-                println!("{}{}{}{}",tab,
+        let mut headed = self.groups.values().filter(|g| g.heading.is_some()).collect::<Vec<_>>();
+        headed.sort_by(|a, b| a.heading.cmp(&b.heading));
+        // The sections with a custom heading, sorted by their heading
+        let mut headed_s: BTreeMap<&str, String> = BTreeMap::new();
+        // Adds the line of an argument to the section of its own heading, or the first group with
+        // a heading it belongs to, or otherwise to its default section
+        let mut push_line = |name: &str, heading: Option<&'ar str>, section: &mut String, line: String| {
+            match heading.or_else(|| headed.iter().filter(|g| g.args.contains(name)).map(|g| g.heading.unwrap()).next()) {
+                Some(h) => headed_s.entry(h).or_insert(String::new()).push_str(&line[..]),
+                None    => section.push_str(&line[..])
+            }
+        };
+        let mut flags_s = String::new();
+        let mut opts_s = String::new();
+        if self.unified_help {
+            // The flags and options are sorted together by their long (or short) version, and
+            // their help is aligned to the longest of either
+            let mut lines = vec![];
+            for v in self.flags.values().filter(|f| !f.hidden) {
+                let h = if long { v.long_help.or(v.help) } else { v.help };
+                lines.push((v.name, v.help_heading, v.long.map_or(v.short.map(|s| s.to_string()), |l| Some(l.to_owned())),
+                    self.unified_switch(v.short, v.long, String::new()), h.unwrap_or(tab).to_owned(),
+                    format!("{}{}{}", self.aliases_help(&v.aliases), deprecated_note!(v), self.relations_help(long, &v.blacklist, &v.requires))));
+            }
+            for v in self.opts.values().filter(|o| !o.env_only() && !o.hidden) {
+                lines.push((v.name, v.help_heading, v.long.map_or(v.short.map(|s| s.to_string()), |l| Some(l.to_owned())),
+                    self.unified_switch(v.short, v.long, v.vals_usage()), get_help!(v, long),
+                    format!("{}{}{}", self.aliases_help(&v.aliases), deprecated_note!(v), self.relations_help(long, &v.blacklist, &v.requires))));
+            }
+            lines.sort_by_key(|&(_, _, ref key, _, _, _)| key.as_ref().map(|k| k.to_lowercase()));
+            let longest = lines.iter().map(|&(_, _, _, ref sw, _, _)| sw.len()).max().unwrap_or(0);
+            for (name, heading, _, sw, h, rest) in lines.into_iter() {
+                let line = format!("{}{}{}", tab, sw, self.get_spaces((longest + 4) - sw.len()));
+                push_line(name, heading, &mut opts_s, format!("{}{}{}\n", line, self.indent_help(&h[..], line.len()), rest));
+            }
+        } else {
+            for v in self.flags.values().filter(|f| !f.hidden) {
+                let line = format!("{}{}{}",tab,
                         if let Some(s) = v.short{format!("-{}",s)}else{tab.to_owned()},
                         if let Some(l) = v.long {
                             format!("{}--{}{}", 
@@ -798,17 +2535,14 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                         } else {
                             // 6 is tab (4) + -- (2)
                             self.get_spaces(longest_flag + 6).to_owned()
-                        },
-                        v.help.unwrap_or(tab) );
+                        });
+                let h = if long { v.long_help.or(v.help) } else { v.help };
+                push_line(v.name, v.help_heading, &mut flags_s, format!("{}{}{}{}{}\n", line, self.indent_help(h.unwrap_or(tab), line.len()),
+                    self.aliases_help(&v.aliases), deprecated_note!(v), self.relations_help(long, &v.blacklist, &v.requires)));
             }
-        }
-        if opts {
-            println!("");
-            println!("OPTIONS:");
-            for v in self.opts.values() {
-                // if it supports multiple we add '...' i.e. 3 to the name length
-                let mult = if v.multiple { 3 } else { 0 };
-                println!("{}{}{}{}{}{}",tab,
+            for v in self.opts.values().filter(|o| !o.env_only() && !o.hidden) {
+                let vals = v.vals_usage();
+                let line = format!("{}{}{}{}{}",tab,
                         if let Some(s) = v.short{format!("-{}",s)}else{tab.to_owned()},
                         if let Some(l) = v.long {
                             format!("{}--{} ", 
@@ -816,43 +2550,125 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                         } else {
                             " ".to_owned()
                         },
-                        format!("<{}>{}", v.name, if v.multiple{"..."} else {""}),
+                        vals,
                         if v.long.is_some() {
-                            self.get_spaces((longest_opt) - (v.long.unwrap().len() + v.name.len() + mult + 1))
+                            self.get_spaces((longest_opt) - (v.long.unwrap().len() + vals.len() - 1))
                         } else {
-                            self.get_spaces((longest_opt + 3) - (v.name.len() + mult))
-                        },
-                        get_help!(v) );
+                            self.get_spaces((longest_opt + 5) - vals.len())
+                        });
+                push_line(v.name, v.help_heading, &mut opts_s, format!("{}{}{}{}{}\n", line, self.indent_help(&get_help!(v, long)[..], line.len()),
+                    self.aliases_help(&v.aliases), deprecated_note!(v), self.relations_help(long, &v.blacklist, &v.requires)));
             }
         }
-        if pos {
-            println!("");
-            println!("POSITIONAL ARGUMENTS:");
-            for v in self.positionals_idx.values() {
-                let mult = if v.multiple { 3 } else { 0 };
-                println!("{}{}{}{}",tab,
-                    if v.multiple {format!("{}...",v.name)} else {v.name.to_owned()},
-                    self.get_spaces((longest_pos + 4) - (v.name.len() + mult)),
-                    get_help!(v));
-            }
+        let mut pos_s = String::new();
+        for v in self.positionals_idx.values().filter(|p| !p.hidden) {
+            let mult = if v.multiple { 3 } else { 0 };
+            let line = format!("{}{}{}",tab,
+                if v.multiple {format!("{}...",v.usage_name())} else {v.usage_name().to_owned()},
+                self.get_spaces((longest_pos + 4) - (v.usage_name().len() + mult)));
+            push_line(v.name, v.help_heading, &mut pos_s, format!("{}{}{}{}\n", line, self.indent_help(&get_help!(v, long)[..], line.len()),
+                deprecated_note!(v), self.relations_help(long, &v.blacklist, &v.requires)));
         }
-        if subcmds {
-            println!("");
-            println!("SUBCOMMANDS:");
-            for sc in self.subcommands.values() {
-                println!("{}{}{}{}",tab,
-                 sc.name,
-                 self.get_spaces((longest_sc + 4) - (sc.name.len())),
-                 if let Some(a) = sc.about {a} else {tab} );
+        let groups = headed_s.into_iter()
+                             .map(|(h, s)| (format!("{}:", h),
+                                            match headed.iter().filter(|g| g.heading == Some(h)).filter_map(|g| g.about).next() {
+                                                Some(a) => format!("{}{}\n{}", tab, a, s),
+                                                None    => s
+                                            }))
+                             .collect::<Vec<_>>();
+        let mut subcmds_s = String::new();
+        for sc in self.subcommands.values().filter(|sc| !sc.hidden) {
+            subcmds_s.push_str(&format!("{}{}{}{}\n",tab,
+             sc.name,
+             self.get_spaces((longest_sc + 4) - (sc.name.len())),
+             if let Some(a) = sc.about {a} else {tab} )[..]);
+        }
+
+        let h = ArgsHelp { flags: flags_s, opts: opts_s, pos: pos_s, subcmds: subcmds_s, groups: groups };
+        self.help_cache.borrow_mut().insert(long, h.clone());
+        h
+    }
+
+    // Renders the short and long version of a flag or option (followed by its values, if any) for
+    // the unified OPTIONS section of the help
+    fn unified_switch(&self, short: Option<char>, long: Option<&str>, vals: String) -> String {
+        let mut sw = match short {
+            Some(s) => format!("-{}", s),
+            None    => "  ".to_owned()
+        };
+        if let Some(l) = long {
+            sw.push_str(&format!("{}--{}", if short.is_some() { ", " } else { "  " }, l)[..]);
+        }
+        if !vals.is_empty() {
+            sw.push_str(&format!(" {}", vals)[..]);
+        }
+        sw
+    }
+
+    // Lists the visible aliases of an argument to be displayed after its help message
+    fn aliases_help(&self, aliases: &Option<Vec<(&str, bool)>>) -> String {
+        if self.strict {
+            return String::new();
+        }
+        let visible = aliases.iter()
+                             .flat_map(|v| v.iter())
+                             .filter(|&&(_, vis)| vis)
+                             .map(|&(a, _)| format!("--{}", a))
+                             .collect::<Vec<_>>();
+        if visible.is_empty() {
+            String::new()
+        } else {
+            format!(" [aliases: {}]", visible.join(", "))
+        }
+    }
+
+    // Lists the arguments an argument conflicts with and requires to be displayed after its long
+    // help message, when describe_relations() is set
+    fn relations_help(&self, long: bool, bl: &Option<HashSet<&str>>, reqs: &Option<HashSet<&str>>) -> String {
+        if !long || !self.help_relations {
+            return String::new();
+        }
+        let mut notes = vec![];
+        for &(title, names) in [("conflicts with", bl), ("requires", reqs)].iter() {
+            if let Some(ref names) = *names {
+                let mut names = names.iter().map(|n| self.relation_name(n)).collect::<Vec<_>>();
+                if names.is_empty() { continue; }
+                names.sort();
+                notes.push(format!("{}: {}", title, names.join(", ")));
             }
         }
+        if notes.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", notes.join("; "))
+        }
+    }
 
-        if let Some(h) = self.more_help {
-            println!("");
-            println!("{}", h);
+    // The name of an argument (or group) as displayed in the relations of another argument
+    fn relation_name(&self, n: &str) -> String {
+        if let Some(o) = self.opts.get(n) {
+            o.switch()
+        } else if let Some(f) = self.flags.get(n) {
+            match f.long {
+                Some(l) => format!("--{}", l),
+                None    => format!("-{}", f.short.unwrap_or(' '))
+            }
+        } else if let Some(p) = self.positionals_idx.values().filter(|p| p.name == n).next() {
+            format!("<{}>", p.usage_name())
+        } else {
+            n.to_owned()
         }
+    }
 
-        self.exit(0);
+    // Indents every line but the first of a (possibly multi-line) help message so that it lines up
+    // with the column the help message starts in
+    fn indent_help(&self, help: &str, col: usize) -> String {
+        let indent: String = (0..col).map(|_| ' ').collect();
+        help.lines()
+            .enumerate()
+            .map(|(i, l)| if i == 0 || l.is_empty() { l.to_owned() } else { format!("{}{}", indent, l) })
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
     // Used when spacing arguments and their help message when displaying help information
@@ -894,63 +2710,351 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
 
     // Prints the version to the user and exits if quit=true
     fn print_version(&self, quit: bool) {
-        // Print the binary name if existing, but replace all spaces with hyphens in case we're
-        // dealing with subcommands i.e. git mv is translated to git-mv
-        println!("{} {}", &self.bin_name.clone().unwrap_or(self.name.clone())[..].replace(" ", "-"), self.version.unwrap_or("") );
-        if quit { self.exit(0); }
+        self.write_version(&mut io::stdout()).unwrap_or(());
+        if quit { self.exit(Exit::Version); }
+    }
+
+    // Prints the detailed version (if one was set) along with any build metadata to the user
+    // and exits
+    fn print_long_version(&self) {
+        if let Some(lv) = self.long_version {
+            println!("{} {}", &self.bin_name.clone().unwrap_or(self.name.clone())[..].replace(" ", "-"), lv);
+        } else {
+            self.print_version(false);
+        }
+        let longest_key = self.version_meta.iter().map(|&(k, _)| k.len()).max().unwrap_or(0);
+        for &(k, v) in self.version_meta.iter() {
+            // 1=':'
+            println!("    {}:{}{}", k, self.get_spaces((longest_key + 2) - (k.len() + 1)), v);
+        }
+        self.exit(Exit::Version);
+    }
+
+    // Reports the time elapsed since start for a phase of parsing to the timing hook, if any
+    #[cfg(feature = "timings")]
+    fn report_timing(&self, phase: Phase, start: Instant) {
+        if let Some(hook) = self.timing_hook {
+            hook(phase, start.elapsed());
+        }
     }
 
-    // Exits with a status code passed to the OS
-    // This is legacy from before std::process::exit() and may be removed evenutally
-    fn exit(&self, status: i32) {
-        process::exit(status);
+    // Exits with the status code set for the reason of exiting (see exit_code_on_error() etc.)
+    fn exit(&self, reason: Exit) {
+        let status = match reason {
+            Exit::Help    => self.help_code.unwrap_or(0),
+            Exit::Version => self.version_code.unwrap_or(0),
+            Exit::Error   => self.error_code.unwrap_or(1)
+        };
+        process::exit(status as i32);
     }
 
-    // Reports and error to the users screen along with an optional usage statement and quits
+    // Reports an error on stderr (or to the function set with error_output()) along with an
+    // optional usage statement and quits
     fn report_error(&self, msg: String, usage: bool, quit: bool) {
-        println!("{}", msg);
-        if usage { self.print_usage(true); }
-        if quit { self.exit(1); }
+        let mut out = format!("{}\n", msg);
+        if usage && !self.quiet_errors {
+            out.push_str(&format!("{}\n", self.create_usage())[..]);
+            // There's no --help to point to if the help flag was disabled (and not replaced)
+            if !self.disable_help || self.long_list.contains("help") {
+                out.push_str("For more information try --help\n");
+            }
+        }
+        self.write_error_output(&out[..]);
+        if quit { self.exit(Exit::Error); }
+    }
+
+    // Writes an error or warning on stderr, or passes it to the function set with error_output()
+    fn write_error_output(&self, out: &str) {
+        match self.error_output {
+            Some(f) => f(out),
+            None    => {
+                use std::io::Write;
+                let _ = write!(io::stderr(), "{}", out);
+            }
+        }
     }
 
     // Starts the parsing process. Called on top level parent app **ONLY** then recursively calls
     // the real parsing function for subcommands
-    pub fn get_matches(mut self) -> ArgMatches<'ar, 'ar> {
+    pub fn get_matches(self) -> ArgMatches<'ar, 'ar> {
+        self.get_matches_os(env::args_os().collect(), true)
+    }
+
+    /// Starts the parsing process with the given arguments (the first of which is the name of the
+    /// binary, as with `std::env::args_os()`), accepting values which aren't valid UTF-8. Whereas
+    /// `get_matches()` reports such arguments as an error, here they're stored as-is so they can
+    /// be retrieved with `ArgMatches::value_of_os()` (i.e. file paths on Unix).
+    ///
+    /// **NOTE:** Flags, options, and subcommands must still be valid UTF-8 to be recognized, only
+    /// values are preserved
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// use std::env;
+    ///
+    /// let matches = App::new("myprog")
+    ///                   .arg(Arg::with_name("file").index(1))
+    ///                   .get_matches_from_os(env::args_os());
+    /// if let Some(f) = matches.value_of_os("file") {
+    ///     println!("Opening {:?}", f);
+    /// }
+    /// ```
+    pub fn get_matches_from_os<I>(self, args: I) -> ArgMatches<'ar, 'ar> where I: IntoIterator<Item=OsString> {
+        self.get_matches_os(args.into_iter().collect(), false)
+    }
+
+    /// Runs the validation which is normally done after parsing (i.e. for required arguments,
+    /// conflicts, requirements, possible values, and numbers of values) on matches which were
+    /// constructed or modified by the program itself, such as when merging in values from a
+    /// config file. Instead of exiting, the error message is returned.
+    ///
+    /// **NOTE:** Every argument in the matches must be defined by this `App`, and subcommands
+    /// aren't validated
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, ArgMatches};
+    /// let mut app = App::new("myprog")
+    ///                   .arg(Arg::with_name("config").long("config").takes_value(true).required(true));
+    /// let mut matches = ArgMatches::new();
+    /// matches.add("config", vec!["my.conf"]);
+    /// if let Err(e) = app.validate(&matches) {
+    ///     println!("Invalid config: {}", e);
+    /// }
+    /// ```
+    pub fn validate(&mut self, matches: &ArgMatches<'ar, 'ar>) -> Result<(), String> {
+        // The state built up while validating is restored afterwards, so the App can be reused
+        let required = self.required.clone();
+        let blacklist = mem::replace(&mut self.blacklist, HashSet::new());
+        self.resolve_conflicts_except();
+        let res = self.validate_matches(matches);
+        self.required = required;
+        self.blacklist = blacklist;
+        res
+    }
+
+    /// Creates a parser which is fed the arguments one at a time, and can be asked what it
+    /// expects next (i.e. to provide hints and validation as-you-type in an interactive shell),
+    /// see `PartialParser`. The first argument supplied is the first one following the name of
+    /// the binary.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// let mut app = App::new("myprog")
+    ///                   .arg(Arg::with_name("config").long("config").takes_value(true));
+    /// let mut parser = app.partial_parser();
+    /// parser.push("--config").unwrap();
+    /// println!("Expected next: {:?}", parser.expected());
+    /// ```
+    pub fn partial_parser<'p>(&'p mut self) -> PartialParser<'p, 'a, 'v, 'ab, 'u, 'h, 'ar> {
+        if self.global_version {
+            self.propagate_version();
+        }
+        self.propagate_globals();
+        self.prepare_partial();
+        PartialParser::new(self)
+    }
+
+    // Adds the help and version arguments (and help subcommand) to the app and all its
+    // subcommands, which is otherwise done as each one is parsed, and closes the gaps in the
+    // indexes of their positional arguments
+    fn prepare_partial(&mut self) {
+        self.create_help_and_version();
+        self.verify_positionals();
+        for sc in self.subcommands.values_mut() {
+            sc.prepare_partial();
+        }
+    }
+
+    // Validates matches which weren't produced by parsing, by first collecting the requirements
+    // and conflicts of each used argument as parsing would, see validate()
+    fn validate_matches(&mut self, matches: &ArgMatches<'ar, 'ar>) -> Result<(), String> {
+        let mut names = matches.args.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        for name in names {
+            let vals = matches.args.get(name).and_then(|ma| ma.values.as_ref());
+            let (bl, reqs) = if let Some(f) = self.flags.get(name) {
+                parse_group_reqs!(self, f);
+                (f.blacklist.clone(), f.requires.clone())
+            } else if let Some(o) = self.opts.get(name) {
+                if let (Some(p_vals), Some(vals)) = (o.possible_vals.as_ref(), vals) {
+                    if let Some(v) = vals.iter().filter(|v| v.to_str().map_or(true, |v| !p_vals.contains(v))).next() {
+                        return Err(format!("{} isn't a valid value for {}\n    [valid values:{}]", quote(&v.to_string_lossy()[..]),
+                            disp_name!(o, o.switch()), p_vals.iter().fold(String::new(), |acc, name| acc + &format!(" {}",name)[..])));
+                    }
+                }
+                if let (Some(n), Some(vals)) = (o.num_vals, vals) {
+                    if n != 0 && vals.len() % n as usize != 0 {
+                        return Err(self.num_vals_msg(o, n as usize, vals.len()));
+                    }
+                }
+                parse_group_reqs!(self, o);
+                (o.blacklist.clone(), o.requires.clone())
+            } else if let Some(p) = self.positionals_idx.values().filter(|p| p.name == name).next() {
+                if let (Some(p_vals), Some(vals)) = (p.possible_vals.as_ref(), vals) {
+                    if let Some(v) = vals.iter().filter(|v| v.to_str().map_or(true, |v| !p_vals.contains(v))).next() {
+                        return Err(format!("{} isn't a valid value for {}\n\t[valid values:{}]", quote(&v.to_string_lossy()[..]),
+                            disp_name!(p, format!("{}", p)), p_vals.iter().fold(String::new(), |acc, name| acc + &format!(" {}",name)[..])));
+                    }
+                }
+                parse_group_reqs!(self, p);
+                (p.blacklist.clone(), p.requires.clone())
+            } else {
+                return Err(format!("Argument \"{}\" isn't defined by {}", name, self.name));
+            };
+            self.required.remove(name);
+            if let Some(bl) = bl {
+                for n in bl {
+                    self.blacklist.insert(n);
+                    self.required.remove(n);
+                }
+            }
+            if let Some(reqs) = reqs {
+                for n in reqs {
+                    if !self.arg_or_group_present(matches, n) {
+                        self.required.insert(n);
+                    }
+                }
+            }
+        }
+
+        try!(self.validate_num_vals(matches));
+
+        try!(self.validate_blacklist(matches));
+
+        self.validate_required_unless(matches);
+
+        self.validate_required_if(matches);
+
+        if !self.required.is_empty() && self.validate_required(matches) {
+            return Err(self.missing_required_msg());
+        }
+        Ok(())
+    }
+
+    // Does the setup shared by get_matches() and get_matches_from_os(), where utf8 determines
+    // whether arguments which aren't valid UTF-8 are reported as an error
+    fn get_matches_os(mut self, args: Vec<OsString>, utf8: bool) -> ArgMatches<'ar, 'ar> {
+        if self.global_version {
+            self.propagate_version();
+        }
+        self.propagate_globals();
         self.verify_positionals();
+        self.verify_groups();
         for (_,sc) in self.subcommands.iter_mut() {
             sc.verify_positionals();
         }
 
         let mut matches = ArgMatches::new();
 
-        let args = env::args().collect::<Vec<_>>();    
+        #[cfg(feature = "timings")]
+        let start = Instant::now();
+        self.utf8_args = utf8;
+        matches.raw_args = args.clone();
         let mut it = args.into_iter();
         if let Some(name) = it.next() {
-            let p = Path::new(&name[..]);
+            let p = Path::new(&name);
             if let Some(f) = p.file_name() {
                 if let Ok(s) = f.to_os_string().into_string() {
                     self.bin_name = Some(s);
                 }
             }
         }
+        #[cfg(feature = "timings")]
+        self.report_timing(Phase::Tokenizing, start);
         self.get_matches_from(&mut matches, &mut it );
 
         matches
     }
     
+    // Copies the version (and detailed version) into all subcommands, recursively, which don't
+    // have their own
+    fn propagate_version(&mut self) {
+        for (_, sc) in self.subcommands.iter_mut() {
+            if sc.version.is_none() {
+                sc.version = self.version;
+            }
+            if sc.long_version.is_none() {
+                sc.long_version = self.long_version;
+            }
+            if sc.version_template.is_none() {
+                sc.version_template = self.version_template;
+            }
+            if sc.version_meta.is_empty() {
+                sc.version_meta = self.version_meta.clone();
+            }
+            sc.propagate_version();
+        }
+    }
+
+    // Adds the global arguments to all subcommands, recursively. An argument of the subcommand
+    // with the same name takes precedence, whereas a different argument using the same short or
+    // long is a conflict the developer has to resolve.
+    fn propagate_globals(&mut self) {
+        let scs = mem::replace(&mut self.subcommands, BTreeMap::new());
+        for (name, mut sc) in scs.into_iter() {
+            for a in self.global_args.iter() {
+                if sc.arg_list.contains(a.name) {
+                    continue;
+                }
+                if let Some(s) = a.short {
+                    if sc.short_list.contains(&s) {
+                        panic!("Global argument \"{}\" conflicts with an argument of the subcommand \"{}\"\n\n\t-{} is already in use\n\n\tEither use a different short, or name the subcommand's argument \"{}\" to override the global one", a.name, sc.name, s, a.name);
+                    }
+                }
+                let mut longs = a.long.into_iter().collect::<Vec<_>>();
+                if let Some(ref aliases) = a.aliases {
+                    longs.extend(aliases.iter().map(|&(l, _)| l));
+                }
+                for l in longs.into_iter() {
+                    if sc.long_list.contains(l) {
+                        panic!("Global argument \"{}\" conflicts with an argument of the subcommand \"{}\"\n\n\t--{} is already in use\n\n\tEither use a different long, or name the subcommand's argument \"{}\" to override the global one", a.name, sc.name, l, a.name);
+                    }
+                }
+                sc = sc.arg(a.clone());
+            }
+            sc.propagate_globals();
+            self.subcommands.insert(name, sc);
+        }
+    }
+
+    fn verify_groups(&self) {
+        // Groups may list arguments by name which were never added to the App (i.e. typos, or
+        // arguments which were later removed), so verify all the members exist before the
+        // missing argument causes a panic in the middle of parsing or creating the usage string
+        for g in self.groups.values() {
+            for n in g.args.iter() {
+                if !self.arg_list.contains(n) {
+                    panic!("ArgGroup \"{}\" contains the argument \"{}\" but no argument with that name was found\n\n\tCheck the spelling, or add the argument to the App", g.name, n);
+                }
+            }
+        }
+        for sc in self.subcommands.values() {
+            sc.verify_groups();
+        }
+    }
+
     fn verify_positionals(&mut self) {
         // Because you must wait until all arguments have been supplied, this is the first chance
         // to make assertions on positional argument indexes
         // 
-        // Firt we verify that the index highest supplied index, is equal to the number of
-        // positional arguments to verify there are no gaps (i.e. supplying an index of 1 and 3
-        // but no 2)
+        // First we close any gaps in the indexes (i.e. supplying an index of 1 and 3 but no 2) by
+        // renumbering the positional arguments in order, so reserved index slots are simply
+        // treated as absent
         //
         // Next we verify that only the highest index has a .multiple(true) (if any)
-        if let Some((idx, ref p)) = self.positionals_idx.iter().rev().next() {
-            if *idx as usize != self.positionals_idx.len() {
-                panic!("Found positional argument \"{}\" who's index is {} but there are only {} positional arguments defined", p.name, idx, self.positionals_idx.len());
-            }
+        let positionals = mem::replace(&mut self.positionals_idx, BTreeMap::new());
+        for (i, (_, mut p)) in positionals.into_iter().enumerate() {
+            p.index = (i + 1) as u8;
+            self.positionals_idx.insert(p.index, p);
         }
         if let Some(ref p) = self.positionals_idx.values()
                                                  .filter(|ref a| a.multiple)
@@ -959,6 +3063,13 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
             panic!("Found positional argument \"{}\" which accepts multiple values but it's not the last positional argument (i.e. others have a higher index)", 
                     p.name);
         }
+        if let Some(ref p) = self.positionals_idx.values()
+                                                 .filter(|ref a| a.last)
+                                                 .filter(|ref a| a.index as usize != self.positionals_idx.len())
+                                                 .next() {
+            panic!("Found positional argument \"{}\" which is only populated after \"--\" but it's not the last positional argument (i.e. others have a higher index)",
+                    p.name);
+        }
         
         // If it's required we also need to ensure all previous positionals are required too
         let mut found = false;
@@ -972,94 +3083,213 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                 found = true;                
             }
         }
-    }
+    }
+
+    fn get_matches_from(&mut self, matches: &mut ArgMatches<'ar, 'ar>, it: &mut IntoIter<OsString>) {
+        if let Some(f) = self.before_parse {
+            f();
+        }
+
+        self.create_help_and_version();
+        // The help may have been written before more arguments were added
+        self.help_cache.borrow_mut().clear();
+
+        #[cfg(debug_assertions)]
+        self.warn_help_takeover();
+
+        self.resolve_conflicts_except();
+
+        if self.arg_required_else_help && it.len() == 0 {
+            self.print_help(false);
+        }
 
-    fn get_matches_from(&mut self, matches: &mut ArgMatches<'ar, 'ar>, it: &mut IntoIter<String>) {
-        self.create_help_and_version();
+        if self.help_overrides_errors {
+            self.check_for_help_and_version_first(it.as_slice());
+        }
 
         let mut pos_only = false;
+        // Whether the remaining arguments are captured verbatim by the trailing positional
+        // argument, see trailing_var_arg()
+        let mut trailing = false;
         let mut subcmd_name: Option<String> = None;
-        let mut needs_val_of: Option<&str> = None; 
+        // The option still requiring values, and how many
+        let mut needs_val_of: Option<(&str, u8)> = None; 
         let mut pos_counter = 1;
-        while let Some(arg) = it.next() {
+        #[cfg(feature = "timings")]
+        let start = Instant::now();
+        while let Some(arg_os) = it.next() {
+            self.cur_idx += 1;
+            // Arguments are matched by their (lossy) string form, whereas values are stored as
+            // supplied, so values which aren't valid UTF-8 are preserved
+            let arg = arg_os.to_string_lossy().into_owned();
             let arg_slice = &arg[..];
+            // Arguments which aren't valid UTF-8 are reported as an error, instead of panicking
+            // as env::args() would
+            if self.utf8_args && arg_os.to_str().is_none() {
+                self.report_error(format!("Invalid UTF-8 was detected in argument {}", quote(arg_slice)), true, true);
+            }
             let mut skip = false;
             if !pos_only {
-                if let Some(nvo) = needs_val_of {
-                    if let Some(ref opt) = self.opts.get(nvo) {
+                if let Some((nvo, left)) = needs_val_of {
+                    // Once all of its required values were consumed, an option with
+                    // multiple_values() consumes values until the next argument starting with '-'
+                    // (unless it accepts such values, and it isn't a valid flag or option)
+                    let hyphen_val = (self.opts.get(nvo).map_or(false, |o| o.allow_hyphen_vals) || self.is_neg_num(arg_slice)) &&
+                                     !self.is_known_arg(arg_slice);
+                    // "--" ends the values, unless the option takes it as a value
+                    let sep_val = arg_slice == "--" && self.opts.get(nvo).map_or(false, |o| o.sep_as_val);
+                    if left == 0 && arg_slice.starts_with("-") && arg_slice.len() != 1 && !hyphen_val && !sep_val {
+                        needs_val_of = None;
+                    } else if arg_slice == "--" && !sep_val {
+                        self.report_missing_vals(nvo, left);
+                    } else if let Some(ref opt) = self.opts.get(nvo) {
+                        let new_vals = opt.split_vals(&arg_os);
+                        // Delimited values only count towards number_of_values() if it's set
+                        if opt.num_vals.is_some() && new_vals.len() > left as usize {
+                            let n = opt.num_vals.unwrap_or(1) as usize;
+                            self.report_error(self.num_vals_msg(opt, n, n - left as usize + new_vals.len()), true, true);
+                        }
+                        let still_needed = (left as usize).saturating_sub(new_vals.len());
                         if let Some(ref p_vals) = opt.possible_vals {
                             if !p_vals.is_empty() {
-                                if !p_vals.contains(arg_slice) {
-                                    self.report_error(format!("\"{}\" isn't a valid value for {}{}", 
-                                                                arg_slice, 
-                                                                if opt.long.is_some() {
-                                                                    format!("--{}",opt.long.unwrap())
-                                                                }else{
-                                                                    format!("-{}", opt.short.unwrap())
-                                                                },
-                                                                format!("\n    [valid values:{}]", p_vals.iter().fold(String::new(), |acc, name| acc + &format!(" {}",name)[..] )) ), true, true);
+                                if let Some(val) = new_vals.iter().filter(|v| v.to_str().map_or(true, |v| !p_vals.contains(v))).next() {
+                                    if let Some(msg) = opt.error_message {
+                                        self.report_error(msg.to_owned(), true, true);
+                                    }
+                                    self.report_error(format!("{} isn't a valid value for {}{}{}", 
+                                                                quote(&val.to_string_lossy()[..]), 
+                                                                disp_name!(opt, opt.switch()),
+                                                                format!("\n    [valid values:{}]", p_vals.iter().fold(String::new(), |acc, name| acc + &format!(" {}",name)[..] )),
+                                                                self.equals_hint(opt, &val.to_string_lossy()[..])), true, true);
                                 }
                             }
                         }
                         if let Some(ref mut o) = matches.args.get_mut(opt.name) {
                             // Options have values, so we can unwrap()
+                            // Values are only ever appended, whether they were supplied as
+                            // "--opt val", "-o val", or "--opt=val", so they're kept in the order
+                            // they were supplied
+                            let idx = self.cur_idx;
+                            o.indices.extend(new_vals.iter().map(|_| idx));
                             if let Some(ref mut vals) = o.values {
-                                vals.push(arg.clone());
+                                vals.extend(new_vals.into_iter());
                             }
      
-                            // if it's multiple the occurrences are increased when originall found
-                            o.occurrences = if opt.multiple { o.occurrences + 1 } else { 1 };
+                            // if it's multiple the occurrences are increased when originall found,
+                            // which is the first value of the occurrence
+                            if left == opt.num_vals.unwrap_or(1) {
+                                o.occurrences = if opt.multiple { o.occurrences + 1 } else { 1 };
+                            }
                         }
-                        
+
+                        needs_val_of = match still_needed {
+                            0 if opt.multiple_vals => Some((nvo, 0)),
+                            0 => None,
+                            l => Some((nvo, l as u8))
+                        };
                         skip = true;
                     }
                 }
             }
             if skip {
-                needs_val_of = None;
                 continue;
             }
 
-            if arg_slice.starts_with("--") && !pos_only {
+            // The next positional argument may accept values starting with '-' (or negative
+            // numbers), as long as they aren't valid flags or options
+            let hyphen_pos = arg_slice.starts_with("-") && !pos_only &&
+                             (self.positionals_idx.get(&pos_counter).map_or(false, |p| p.allow_hyphen_vals) ||
+                              self.is_neg_num(arg_slice)) &&
+                             !self.is_known_arg(arg_slice);
+            if arg_slice.starts_with("--") && !pos_only && !hyphen_pos {
                 if arg_slice.len() == 2 {
                     pos_only = true;
+                    matches.trailing_sep = true;
+                    // The remaining arguments belong to the positional argument which is only
+                    // populated after "--" (if any)
+                    if let Some(p) = self.positionals_idx.values().filter(|p| p.last).next() {
+                        pos_counter = p.index;
+                    }
                     continue;
                 }
                 // Single flag, or option long version
-                needs_val_of = self.parse_long_arg(matches, &arg);
-            } else if arg_slice.starts_with("-") && arg_slice.len() != 1 && ! pos_only {
+                needs_val_of = self.parse_long_arg(matches, &arg, &arg_os);
+            } else if arg_slice.starts_with("-") && arg_slice.len() != 1 && ! pos_only && !hyphen_pos {
                 needs_val_of = self.parse_short_arg(matches, &arg);
             } else {
                 // Positional or Subcommand
-                if self.subcommands.contains_key(&arg) {
-                    if arg_slice == "help" {
-                        self.print_help();
+                if !(pos_only && self.pos_after_sep) && !trailing && self.subcommands.contains_key(&arg) {
+                    if arg_slice == "help" && self.needs_subcmd_help {
+                        self.print_help(true);
                     }
                     subcmd_name = Some(arg.clone());
                     break;
                 }
 
+                if !(pos_only && self.pos_after_sep) && !trailing && self.infer_subcmds {
+                    if let Some(sc) = self.infer_subcommand(arg_slice) {
+                        if sc == "help" && self.needs_subcmd_help {
+                            self.print_help(true);
+                        }
+                        subcmd_name = Some(sc);
+                        break;
+                    }
+                }
+
+                if self.positionals_idx.is_empty() && self.allow_external_subcmds && !pos_only {
+                    // Capture the remaining arguments as-is for the external subcommand
+                    let vals = it.by_ref().collect::<Vec<_>>();
+                    if self.utf8_args && !self.allow_invalid_utf8_ext {
+                        if let Some(v) = vals.iter().filter(|v| v.to_str().is_none()).next() {
+                            self.report_error(format!("Invalid UTF-8 was detected in argument {}", quote(&v.to_string_lossy()[..])), true, true);
+                        }
+                    }
+                    let mut new_matches = ArgMatches::new();
+                    new_matches.raw_args = matches.raw_args.clone();
+                    new_matches.args.insert("", MatchedArg{
+                        occurrences: vals.len() as u64,
+                        indices: (self.cur_idx + 1..self.cur_idx + 1 + vals.len()).collect(),
+                        values: Some(vals),
+                        default: false
+                    });
+                    matches.subcommand = Some(Box::new(SubCommand{
+                        name: Cow::Owned(arg.clone()),
+                        matches: new_matches}));
+                    break;
+                }
+
                 if self.positionals_idx.is_empty() {
+                    self.check_subcommand_typo(arg_slice);
                     self.report_error(
-                        format!("Argument \"{}\" isn't a valid option for {}", arg, self.bin_name.clone().unwrap_or(self.name.clone())),
+                        format!("Argument {} isn't a valid option for {}{}", quote(arg_slice), self.bin_name.clone().unwrap_or(self.name.clone()),
+                            self.repeated_subcommand_hint(arg_slice)),
                         true, true);
                 }
                 // If we find that an argument requires a positiona, we need to update all the
                 // previous positionals too. This will denote where to start
                 // let mut req_pos_from_name = None;
                 if let Some(p) = self.positionals_idx.get(&pos_counter) {
+                    if p.last && !matches.trailing_sep {
+                        self.report_error(format!("Argument {} isn't valid here, values for {} must follow \"--\"", quote(arg_slice), disp_name!(p, format!("\"{}\"", p))),
+                            true, true);
+                    }
                     if self.blacklist.contains(p.name) {
-                        self.report_error(format!("The argument \"{}\" cannot be used with one or more of the other specified arguments", p),
+                        self.report_error(format!("The argument {} cannot be used with one or more of the other specified arguments", disp_name!(p, format!("\"{}\"", p))),
                             true, true);
                     }
 
                     if let Some(ref p_vals) = p.possible_vals {
                         if !p_vals.is_empty() {
                             if !p_vals.contains(arg_slice) {
-                                self.report_error(format!("\"{}\" isn't a valid value for {}{}", 
-                                    arg_slice, 
-                                    p,
-                                    format!("\n\t[valid values:{}]", p_vals.iter().fold(String::new(), |acc, name| acc + &format!(" {}",name)[..] )) ), true, true);
+                                self.check_subcommand_typo(arg_slice);
+                                if let Some(msg) = p.error_message {
+                                    self.report_error(msg.to_owned(), true, true);
+                                }
+                                self.report_error(format!("{} isn't a valid value for {}{}{}", 
+                                    quote(arg_slice), 
+                                    disp_name!(p, format!("{}", p)),
+                                    format!("\n\t[valid values:{}]", p_vals.iter().fold(String::new(), |acc, name| acc + &format!(" {}",name)[..] )),
+                                    self.repeated_subcommand_hint(arg_slice)), true, true);
                             }
                         }
                     }
@@ -1070,8 +3300,9 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                         if let Some(ref mut pos) = matches.args.get_mut(p.name) {
                             done = true;
                             pos.occurrences += 1;
+                            pos.indices.push(self.cur_idx);
                             if let Some(ref mut vals) = pos.values {
-                                vals.push(arg.clone());
+                                vals.push(arg_os.clone());
                             }
                         }
                     } else {
@@ -1082,10 +3313,19 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                     if !done {
                         matches.args.insert(p.name, MatchedArg{
                             occurrences: 1,
-                            values: Some(vec![arg.clone()]),
+                            indices: vec![self.cur_idx],
+                            values: Some(vec![arg_os.clone()]),
+                            default: false,
                         });
                     }
 
+                    self.override_args(matches, p.name);
+
+                    if self.trailing_var_arg && p.multiple && p.index as usize == self.positionals_idx.len() {
+                        pos_only = true;
+                        trailing = true;
+                    }
+
                     if let Some(ref bl) = p.blacklist {
                         for name in bl {
                             self.blacklist.insert(name);
@@ -1099,7 +3339,7 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                         // final required list
                         for n in reqs {
                             self.matched_reqs.insert(n);
-                            if matches.args.contains_key(n) {continue;}
+                            if self.arg_or_group_present(matches, n) { continue; }
 
                             self.required.insert(n);
                         }
@@ -1108,77 +3348,203 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                     parse_group_reqs!(self, p);
 
                 } else {
-                    self.report_error(format!("Argument \"{}\" isn't a valid argument for {}", arg, self.bin_name.clone().unwrap_or(self.name.clone())), true, true);
+                    self.check_subcommand_typo(arg_slice);
+                    self.report_error(format!("Argument {} isn't a valid argument for {}{}", quote(arg_slice), self.bin_name.clone().unwrap_or(self.name.clone()),
+                        self.repeated_subcommand_hint(arg_slice)), true, true);
                 }
             }
         }
+        #[cfg(feature = "timings")]
+        self.report_timing(Phase::Matching, start);
+        #[cfg(feature = "timings")]
+        let start = Instant::now();
         match needs_val_of {
-            Some(ref a) => {
-                self.report_error(
-                    format!("Argument \"{}\" requires a value but none was supplied", a),
-                    true, true);
+            Some((a, left)) if left > 0 => {
+                self.report_missing_vals(a, left);
             }
             _ => {}
         }
 
-        self.validate_blacklist(&matches);
+        if self.subcmd_required && subcmd_name.is_none() && matches.subcommand.is_none() {
+            self.report_error(format!("{} requires a subcommand, but none was supplied",
+                self.bin_name.clone().unwrap_or(self.name.clone())), true, true);
+        }
+
+        self.add_env(matches);
+
+        self.warn_deprecated(&matches);
+
+        if let Err(e) = self.validate_num_vals(&matches) {
+            self.report_error(e, true, true);
+        }
+
+        if let Err(e) = self.validate_blacklist(&matches) {
+            self.report_error(e, true, true);
+        }
+
+        self.add_defaults(matches);
+
+        self.validate_required_unless(matches);
+
+        self.validate_required_if(matches);
 
         if !self.required.is_empty() {
             // println!("reqs: {:?}", self.required);
             // println!("bls:  {:?}", self.blacklist);
             // println!("grps: {:?}", self.groups);
             if self.validate_required(&matches) {
-                self.report_error("One or more required arguments were not supplied".to_owned(),
-                        true, true);
+                self.report_error(self.missing_required_msg(), true, true);
             }
         }
 
+        for g in self.groups.values() {
+            if let Some(n) = g.args.iter().filter(|n| matches.args.contains_key(*n)).next() {
+                matches.groups.insert(g.name, n);
+            }
+        }
+
+        #[cfg(feature = "timings")]
+        self.report_timing(Phase::Validating, start);
+
         matches.usage = Some(self.create_usage());
+        matches.bin_name = self.bin_name.clone();
+        matches.error_output = self.error_output;
+        matches.error_code = self.error_code;
+
+        // Remembers how each argument with values is displayed, so errors about parsing its values
+        // (i.e. from value_t!) can include the expected format and its help
+        let names = matches.args.keys().cloned().collect::<Vec<_>>();
+        for name in names {
+            let help = if let Some(o) = self.opts.get(name) {
+                Some((format!("{}", o), o.help))
+            } else {
+                self.positionals_idx.values()
+                                    .filter(|p| p.name == name)
+                                    .next()
+                                    .map(|p| (format!("{}", p), p.help))
+            };
+            if let Some(h) = help {
+                matches.arg_help.insert(name, h);
+            }
+        }
 
         if let Some(sc_name) = subcmd_name {
             if let Some(ref mut sc) = self.subcommands.get_mut(&sc_name) {
                 let mut new_matches = ArgMatches::new();
+                new_matches.raw_args = matches.raw_args.clone();
+                if self.quiet_errors { sc.quiet_errors = true; }
+                if sc.error_code.is_none() { sc.error_code = self.error_code; }
+                if sc.help_code.is_none() { sc.help_code = self.help_code; }
+                if sc.version_code.is_none() { sc.version_code = self.version_code; }
+                sc.utf8_args = self.utf8_args;
+                sc.cur_idx = self.cur_idx;
+                sc.subcmd_path = self.subcmd_path.clone();
+                sc.subcmd_path.push(sc.name.clone());
+                if sc.error_output.is_none() { sc.error_output = self.error_output; }
+                for s in self.g_settings.iter() {
+                    sc.set(*s, true);
+                    sc.g_settings.push(*s);
+                }
+                #[cfg(feature = "timings")]
+                {
+                    if sc.timing_hook.is_none() { sc.timing_hook = self.timing_hook; }
+                }
                 // bin_name should be parent's bin_name + the sc's name seperated by a space
                 sc.bin_name = Some(format!("{}{}{}", self.bin_name.clone().unwrap_or("".to_owned()),if self.bin_name.is_some() {" "} else {""}, sc.name.clone()));
+                if let Some(u) = self.usage_str {
+                    match sc.parent_usage {
+                        ParentUsage::Inherit => if sc.usage_str.is_none() { sc.usage_str = Some(u); },
+                        ParentUsage::Prefix  => sc.usage_prefix = Some(format!("{} {}", u.trim_right(), sc.name)),
+                        ParentUsage::Ignore  => ()
+                    }
+                }
                 sc.get_matches_from(&mut new_matches, it);
                 matches.subcommand = Some(Box::new(SubCommand{
-                    name: sc.name_slice,
+                    name: Cow::Borrowed(sc.name_slice),
                     matches: new_matches}));
             }
         }    
+
+        if let Some(f) = self.after_parse {
+            f(matches);
+        }
     }
 
-    fn create_help_and_version(&mut self) {
+    // Builds the automatic help flag (with the customizations made with mut_arg(), if any), unless
+    // it was disabled or taken over
+    fn help_flag(&self) -> Option<FlagBuilder<'ar>> {
+        if !self.needs_long_help {
+            return None;
+        }
         // name is "hclap_help" because flags are sorted by name
-        if self.needs_long_help {
-            let mut arg = FlagBuilder {
-                name: "hclap_help",
-                short: None,
-                long: Some("help"),
-                help: Some("Prints help information"),
-                blacklist: None,
-                multiple: false,
-                requires: None,
-            };
-            if self.needs_short_help {
-                arg.short = Some('h');
-            }
+        Some(self.auto_flag("hclap_help", "help", "Prints help information", &self.help_arg,
+            if self.needs_short_help { self.help_short() } else { None }))
+    }
+
+    // Builds the automatic version flag, see help_flag()
+    fn version_flag(&self) -> Option<FlagBuilder<'ar>> {
+        if !self.needs_long_version {
+            return None;
+        }
+        // name is "vclap_version" because flags are sorted by name
+        Some(self.auto_flag("vclap_version", "version", "Prints version information", &self.version_arg,
+            if self.needs_short_version { self.version_short() } else { None }))
+    }
+
+    fn auto_flag(&self, name: &'ar str, long: &'ar str, help: &'ar str, custom: &Option<Arg<'ar, 'ar, 'ar, 'ar, 'ar, 'ar>>,
+                 short: Option<char>) -> FlagBuilder<'ar> {
+        let mut arg = FlagBuilder {
+            name: name,
+            short: short,
+            long: Some(long),
+            aliases: None,
+            strict: self.strict,
+            help: Some(help),
+            long_help: None,
+            blacklist: None,
+            overrides: None,
+            multiple: false,
+            requires: None,
+            groups: vec![],
+            error_message: None,
+            disp_name: None,
+            deprecated: None,
+            hidden: false,
+            help_heading: None,
+        };
+        if let Some(ref a) = *custom {
+            arg.help = a.help;
+            arg.long_help = a.long_help;
+            arg.hidden = a.hidden;
+            arg.help_heading = a.help_heading;
+        }
+        arg
+    }
+
+    // The short of the automatic help flag, which is -h unless changed with mut_arg()
+    fn help_short(&self) -> Option<char> {
+        self.help_arg.as_ref().map_or(Some('h'), |a| a.short)
+    }
+
+    // The short of the automatic version flag, which is -v unless changed with mut_arg()
+    fn version_short(&self) -> Option<char> {
+        self.version_arg.as_ref().map_or(Some('v'), |a| a.short)
+    }
+
+    // The automatic help and version flags which weren't added yet (that happens once parsing
+    // begins), i.e. for the man page of an app which isn't parsed
+    pub(crate) fn pending_auto_flags(&self) -> Vec<FlagBuilder<'ar>> {
+        self.help_flag().into_iter()
+                        .chain(self.version_flag().into_iter())
+                        .filter(|f| !self.flags.contains_key(f.name))
+                        .collect()
+    }
+
+    fn create_help_and_version(&mut self) {
+        if let Some(arg) = self.help_flag() {
             self.flags.insert("hclap_help", arg);
         }
-        if self.needs_long_version {
-            // name is "vclap_version" because flags are sorted by name
-            let mut arg = FlagBuilder {
-                name: "vclap_version",
-                short: None,
-                long: Some("version"),
-                help: Some("Prints version information"),
-                blacklist: None,
-                multiple: false,
-                requires: None,
-            };
-            if self.needs_short_version {
-                arg.short = Some('v');
-            }
+        if let Some(arg) = self.version_flag() {
             self.flags.insert("vclap_version", arg);
         }
         if self.needs_subcmd_help && !self.subcommands.is_empty() {
@@ -1186,75 +3552,216 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
         }
     }
 
+    // Displays a warning on stderr (in debug builds only) if an argument took over --help or
+    // --version, so no help or version flag is generated, unless the argument is named after the
+    // flag it replaces (i.e. "help") or the flag was disabled, which make the takeover evidently
+    // intended
+    #[cfg(debug_assertions)]
+    fn warn_help_takeover(&self) {
+        let longs = self.flags.values().map(|f| (f.name, f.long))
+                        .chain(self.opts.values().map(|o| (o.name, o.long)));
+        for (name, long) in longs {
+            let flag = match long {
+                Some("help") if !self.needs_long_help && !self.disable_help                                => "help",
+                Some("version") if !self.needs_long_version && !self.disable_version && self.version.is_some() => "version",
+                _                                                                                          => continue
+            };
+            if name.to_lowercase() != flag {
+                self.write_error_output(&format!("warning: the argument \"{}\" uses --{}, so no {} flag is generated for {}\n\n\tIf this is intended, name the argument \"{}\" or disable the flag with AppSettings::Disable{}Flag\n",
+                    name, flag, flag, self.bin_name.clone().unwrap_or(self.name.clone()), flag,
+                    if flag == "help" { "Help" } else { "Version" })[..]);
+            }
+        }
+    }
+
+    // Adds every argument which isn't explicitly allowed (by name or group) to the conflicts of
+    // the arguments set with conflicts_with_everything_except()
+    fn resolve_conflicts_except(&mut self) {
+        for &(name, ref except) in self.conflicts_except.iter() {
+            let allowed = |n: &str| n == name || n == "hclap_help" || n == "vclap_version" || except.iter().any(|&e| {
+                e == n || self.groups.get(e).map_or(false, |g| g.args.contains(n))
+            });
+            let names = self.flags.keys().cloned()
+                            .chain(self.opts.keys().cloned())
+                            .chain(self.positionals_idx.values().map(|p| p.name))
+                            .filter(|n| !allowed(n))
+                            .collect::<HashSet<_>>();
+            if names.is_empty() {
+                continue;
+            }
+            if let Some(f) = self.flags.get_mut(name) {
+                f.blacklist.get_or_insert(HashSet::new()).extend(names);
+            } else if let Some(o) = self.opts.get_mut(name) {
+                o.blacklist.get_or_insert(HashSet::new()).extend(names);
+            } else if let Some(p) = self.positionals_idx.values_mut().filter(|p| p.name == name).next() {
+                p.blacklist.get_or_insert(HashSet::new()).extend(names);
+            }
+        }
+    }
+
+    // Finds the only (visible) subcommand starting with the given prefix, or reports an error if
+    // there are multiple
+    fn infer_subcommand(&self, prefix: &str) -> Option<String> {
+        let candidates = self.subcommands.values()
+                                         .filter(|sc| !sc.hidden && sc.name.starts_with(prefix))
+                                         .map(|sc| sc.name.clone())
+                                         .collect::<Vec<_>>();
+        if candidates.len() > 1 {
+            self.report_error(format!("The subcommand {} is ambiguous\n\n\tIt could be any of: {}",
+                quote(prefix), candidates.join(", ")), true, true);
+        }
+        candidates.into_iter().next()
+    }
+
+    // Finds the long (or alias) which is either exactly the given name, or the only one starting
+    // with it, or reports an error if multiple start with it
+    fn infer_long(&self, name: &str) -> Option<&'ar str> {
+        let mut longs = vec![];
+        for (long, aliases) in self.flags.values().map(|f| (f.long, &f.aliases))
+                                               .chain(self.opts.values().map(|o| (o.long, &o.aliases))) {
+            longs.extend(long.into_iter());
+            if let Some(ref a) = *aliases {
+                longs.extend(a.iter().map(|&(a, _)| a));
+            }
+        }
+        if let Some(&l) = longs.iter().filter(|&&l| l == name).next() {
+            return Some(l);
+        }
+        let mut candidates = longs.into_iter().filter(|l| l.starts_with(name)).collect::<Vec<_>>();
+        candidates.sort();
+        if candidates.len() > 1 {
+            self.report_error(format!("The argument {} is ambiguous\n\n\tIt could be any of: {}",
+                quote(&format!("--{}", name)[..]),
+                candidates.iter().map(|c| format!("--{}", c)).collect::<Vec<_>>().join(", ")), true, true);
+        }
+        candidates.into_iter().next()
+    }
+
+    // Displays the help or version information if requested anywhere in the arguments for this
+    // App, before any of the arguments are parsed (and thus before any errors could be reported)
+    fn check_for_help_and_version_first(&self, args: &[OsString]) {
+        let is_short = |a: &str, s: Option<char>| s.map_or(false, |s| a == format!("-{}", s));
+        for arg in args.iter() {
+            match &arg.to_string_lossy()[..] {
+                "--"                                   => return,
+                "--help" if self.needs_long_help       => self.print_help(true),
+                a if self.needs_short_help && is_short(a, self.help_short()) => self.print_help(false),
+                "--version" if self.needs_long_version => self.print_long_version(),
+                a if self.needs_short_version && is_short(a, self.version_short()) => self.print_version(true),
+                // The remaining arguments belong to the subcommand
+                a if self.subcommands.contains_key(a)  => return,
+                _                                      => ()
+            }
+        }
+    }
+
     fn check_for_help_and_version(&self, arg: char) {
-        if arg == 'h' && self.needs_short_help {
-            self.print_help();
-        } else if arg == 'v' && self.needs_short_version {
+        if Some(arg) == self.help_short() && self.needs_short_help {
+            self.print_help(false);
+        } else if Some(arg) == self.version_short() && self.needs_short_version {
             self.print_version(true);
         }
     }
 
-    fn parse_long_arg(&mut self, matches: &mut ArgMatches<'ar, 'ar> ,full_arg: &String) -> Option<&'ar str> {
+    // Returns the option requiring values, if any, and how many it requires
+    fn parse_long_arg(&mut self, matches: &mut ArgMatches<'ar, 'ar> ,full_arg: &String, full_os: &OsString) -> Option<(&'ar str, u8)> {
         let mut arg = full_arg.trim_left_matches(|c| c == '-');
 
         if arg == "help" && self.needs_long_help {
-            self.print_help();
+            self.print_help(true);
         } else if arg == "version" && self.needs_long_version {
-            self.print_version(true);
+            self.print_long_version();
         }
 
-        let mut arg_val: Option<String> = None;
+        let mut arg_val: Option<OsString> = None;
 
-        if arg.contains("=") {
-            let arg_vec: Vec<&str> = arg.split("=").collect();
-            arg = arg_vec[0];
+        if let Some(i) = arg.find('=') {
+            // The value is taken from the argument as supplied, since it may not be valid UTF-8
+            let val = osstr::after(full_os, '=').unwrap_or(OsString::new());
+            arg = &arg[..i];
             // prevents "--config= value" typo
-            if arg_vec[1].len() == 0 {
-                self.report_error(format!("Argument --{} requires a value, but none was supplied", arg), true, true);
+            if val.is_empty() {
+                let placeholder = self.opts.values()
+                                           .filter(|o| o.long == Some(arg))
+                                           .next()
+                                           .map_or("<value>".to_owned(), |o| o.vals_usage());
+                self.report_error(format!("Argument --{0} requires a value, but none was supplied\n\n\t\
+                    There must be no space after '=', i.e. --{0}={1} or --{0} {1}", arg, placeholder), true, true);
             }
-            arg_val = Some(arg_vec[1].to_owned());
+            arg_val = Some(val);
         } 
 
-        if let Some(v) = self.opts.values().filter(|&v| v.long.is_some()).filter(|&v| v.long.unwrap() == arg).nth(0) {
+        if self.infer_long_args {
+            if let Some(l) = self.infer_long(arg) {
+                arg = l;
+            }
+            if arg == "help" && self.needs_long_help {
+                self.print_help(true);
+            } else if arg == "version" && self.needs_long_version {
+                self.print_long_version();
+            }
+        }
+
+        if let Some(v) = self.opts.values().filter(|&v| v.long == Some(arg) || has_alias!(v, arg)).nth(0) {
             // Ensure this option isn't on the master mutually excludes list
             if self.blacklist.contains(v.name) {
-                self.report_error(format!("The argument --{} cannot be used with one or more of the other specified arguments", arg),
+                self.report_error(format!("The argument {} cannot be used with one or more of the other specified arguments", disp_name!(v, format!("--{}", arg))),
                     true, true);
             }
 
+            // An option whose value is optional doesn't consume the next argument when used bare
+            let bare = v.optional_val() && arg_val.is_none();
+            let new_vals = if bare {
+                Some(v.default_missing_val.map(|d| vec![OsString::from(d)]).unwrap_or(vec![]))
+            } else {
+                arg_val.as_ref().map(|av| v.split_vals(av))
+            };
+            let num_vals = v.num_vals.unwrap_or(1) as usize;
+            let supplied = new_vals.as_ref().map(|nv| nv.len()).unwrap_or(0);
+            if v.num_vals.is_some() && supplied > num_vals {
+                self.report_error(self.num_vals_msg(v, num_vals, supplied), true, true);
+            }
+            if let Some(ref p_vals) = v.possible_vals {
+                if let Some(ref nv) = new_vals {
+                    if let Some(val) = nv.iter().filter(|nv| nv.to_str().map_or(true, |nv| !p_vals.contains(nv))).next() {
+                        if let Some(msg) = v.error_message {
+                            self.report_error(msg.to_owned(), true, true);
+                        }
+                        self.report_error(format!("{} isn't a valid value for {}{}", 
+                                                    quote(&val.to_string_lossy()[..]), 
+                                                    disp_name!(v, v.switch()),
+                                                    format!("\n    [valid values:{}]", p_vals.iter().fold(String::new(), |acc, name| acc + &format!(" {}",name)[..] )) ), true, true);
+                    }
+                }
+            }
+
             if matches.args.contains_key(v.name) {
                 if !v.multiple {
-                    self.report_error(format!("Argument --{} was supplied more than once, but does not support multiple values", arg), true, true);
+                    self.report_error(format!("Argument {} was supplied more than once, but does not support multiple occurrences", disp_name!(v, format!("--{}", arg))), true, true);
                 }
-                if let Some(ref p_vals) = v.possible_vals {
-                    if let Some(ref av) = arg_val {
-                        if !p_vals.contains(&av[..]) {
-                            self.report_error(format!("\"{}\" isn't a valid value for {}{}", 
-                                                        arg_val.clone().unwrap_or(arg.to_owned()), 
-                                                        if v.long.is_some() {
-                                                            format!("--{}", v.long.unwrap())
-                                                        }else{
-                                                            format!("-{}", v.short.unwrap())
-                                                        },
-                                                        format!("\n    [valid values:{}]", p_vals.iter().fold(String::new(), |acc, name| acc + &format!(" {}",name)[..] )) ), true, true);
-                        }
-                    }
+                if v.req_delim {
+                    self.report_error(format!("Argument {} was supplied more than once, but its values must be separated by {} instead", disp_name!(v, format!("--{}", arg)), quote(&v.val_delim.unwrap().to_string()[..])), true, true);
                 }
-                if arg_val.is_some() {
+                if let Some(nv) = new_vals.clone() {
                     if let Some(ref mut o) = matches.args.get_mut(v.name) {
                         o.occurrences += 1;
+                        o.indices.extend(nv.iter().map(|_| self.cur_idx));
                         if let Some(ref mut vals) = o.values {
-                            vals.push(arg_val.clone().unwrap());
+                            vals.extend(nv.into_iter());
                         }
                     }
                 }
             } else {
                 matches.args.insert(v.name, MatchedArg{
                     // name: v.name.to_owned(),
-                    occurrences: if arg_val.is_some() { 1 } else { 0 },
-                    values: if arg_val.is_some() { Some(vec![arg_val.clone().unwrap()])} else { Some(vec![]) }
+                    occurrences: if new_vals.is_some() { 1 } else { 0 },
+                    indices: new_vals.as_ref().map_or(vec![], |nv| nv.iter().map(|_| self.cur_idx).collect()),
+                    values: Some(new_vals.clone().unwrap_or(vec![])),
+                    default: false
                 });
             }
+
+            self.override_args(matches, v.name);
             
             if let Some(ref bl) = v.blacklist {
                 for name in bl {
@@ -1270,7 +3777,7 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                 // final required list
                 for n in reqs {
                     self.matched_reqs.insert(n);
-                    if matches.args.contains_key(n) { continue; }
+                    if self.arg_or_group_present(matches, n) { continue; }
 
                     self.required.insert(n);
                 }
@@ -1278,22 +3785,30 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
 
             parse_group_reqs!(self, v);
 
-            match arg_val {
-                None => { return Some(v.name); },
-                _    => { return None; }
+            if bare { return None; }
+
+            match num_vals.saturating_sub(supplied) {
+                0 if v.multiple_vals => { return Some((v.name, 0)); },
+                0 => { return None; },
+                l => { return Some((v.name, l as u8)); }
             }
         } 
 
-        if let Some(v) = self.flags.values().filter(|&v| v.long.is_some()).filter(|&v| v.long.unwrap() == arg).nth(0) {
+        if let Some(v) = self.flags.values().filter(|&v| v.long == Some(arg) || has_alias!(v, arg)).nth(0) {
+            // Flags don't take values, so "--flag=value" is most likely a mistake
+            if let Some(ref av) = arg_val {
+                self.report_error(format!("Argument {} doesn't take a value, but {} was supplied\n\n\tUse --{} on its own",
+                    disp_name!(v, format!("--{}", arg)), quote(&av.to_string_lossy()[..]), arg), true, true);
+            }
             // Ensure this flag isn't on the mutually excludes list
             if self.blacklist.contains(v.name) {
-                self.report_error(format!("The argument {} cannot be used with one or more of the other specified arguments", v),
+                self.report_error(format!("The argument {} cannot be used with one or more of the other specified arguments", disp_name!(v, format!("{}", v))),
                     true, true);
             }
             
             // Make sure this isn't one being added multiple times if it doesn't suppor it
             if matches.args.contains_key(v.name) && !v.multiple {
-                self.report_error(format!("Argument {} was supplied more than once, but does not support multiple values", v), true, true);
+                self.report_error(format!("Argument {} was supplied more than once, but does not support multiple values", disp_name!(v, format!("{}", v))), true, true);
             }
 
             let mut 
@@ -1301,15 +3816,20 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
             if let Some(ref mut f) = matches.args.get_mut(v.name) {
                 done = true;
                 f.occurrences = if v.multiple { f.occurrences + 1 } else { 1 };
+                f.indices.push(self.cur_idx);
             }
             if !done { 
                 matches.args.insert(v.name, MatchedArg{
                     // name: v.name.to_owned(),
                     occurrences: 1,
-                    values: None
+                    indices: vec![self.cur_idx],
+                    values: None,
+                    default: false
                 });
             }
 
+            self.override_args(matches, v.name);
+
             // If this flag was requierd, remove it
             // .. even though Flags shouldn't be required
             self.required.remove(v.name);
@@ -1326,7 +3846,7 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
             if let Some(ref reqs) = v.requires {
                 for n in reqs {
                     self.matched_reqs.insert(n);
-                    if matches.args.contains_key(n) { continue; }
+                    if self.arg_or_group_present(matches, n) { continue; }
 
                     self.required.insert(n);
                 }
@@ -1338,25 +3858,37 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
         }
 
         // Shouldn't reach here
-        self.report_error(format!("Argument --{} isn't valid", arg), true, true);
+        self.report_error(format!("Argument {} isn't valid{}", quote(full_arg), self.positional_hint(matches, full_arg)), true, true);
         // Can't reach here...
         unreachable!();
     }
 
-    fn parse_short_arg(&mut self, matches: &mut ArgMatches<'ar, 'ar> ,full_arg: &String) -> Option<&'ar str> {
+    // Returns the option requiring values, if any, and how many it requires
+    fn parse_short_arg(&mut self, matches: &mut ArgMatches<'ar, 'ar> ,full_arg: &String) -> Option<(&'ar str, u8)> {
         let arg = &full_arg[..].trim_left_matches(|c| c == '-');
-        if arg.len() > 1 { 
-            // Multiple flags using short i.e. -bgHlS
-            for c in arg.chars() {
+        // Count chars instead of bytes so that a single multi-byte short (i.e. -ä) isn't
+        // mistaken for a cluster
+        let last = arg.chars().count() - 1;
+        // Verify the entire cluster first, so that an invalid short is reported instead of acting
+        // on any of the valid ones (such as -h)
+        if let Some((c, msg)) = self.short_cluster_error(arg) {
+            let hint = if self.opts.values().any(|o| o.short == Some(c)) {
+                String::new()
+            } else {
+                format!("{}{}", self.short_hint(c, arg), self.positional_hint(matches, full_arg))
+            };
+            self.report_error(format!("{}{}", msg, hint), true, true);
+        }
+        if last > 0 { 
+            // Multiple flags using short i.e. -bgHlS, the last of which may also be an option
+            // taking the next argument as its value, i.e. -bgo val
+            for c in arg.chars().take(last) {
                 self.check_for_help_and_version(c);
-                if !self.parse_single_short_flag(matches, c) { 
-                    self.report_error(format!("Argument -{} isn't valid",arg), true, true);
-                }
+                self.parse_single_short_flag(matches, c);
             }
-            return None;
         } 
-        // Short flag or opt
-        let arg_c = arg.chars().nth(0).unwrap();
+        // Short flag or opt, or the last one of a cluster
+        let arg_c = arg.chars().nth(last).unwrap();
 
         // Ensure the arg in question isn't a help or version flag
         self.check_for_help_and_version(arg_c);
@@ -1369,22 +3901,28 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
         if let Some(v) = self.opts.values().filter(|&v| v.short.is_some()).filter(|&v| v.short.unwrap() == arg_c).nth(0) {
             // Ensure this option isn't on the master mutually excludes list
             if self.blacklist.contains(v.name) {
-                self.report_error(format!("The argument -{} cannot be used with one or more of the other specified arguments", arg),
+                self.report_error(format!("The argument {} cannot be used with one or more of the other specified arguments", disp_name!(v, format!("-{}", arg_c))),
                     true, true);
             }
 
             if matches.args.contains_key(v.name) {
                 if !v.multiple {
-                    self.report_error(format!("Argument -{} was supplied more than once, but does not support multiple values", arg), true, true);
+                    self.report_error(format!("Argument {} was supplied more than once, but does not support multiple occurrences", disp_name!(v, format!("-{}", arg_c))), true, true);
+                }
+                if v.req_delim {
+                    self.report_error(format!("Argument {} was supplied more than once, but its values must be separated by {} instead", disp_name!(v, format!("-{}", arg_c)), quote(&v.val_delim.unwrap().to_string()[..])), true, true);
                 }
             } else {
                 matches.args.insert(v.name, MatchedArg{
                     // name: v.name.to_owned(),
                     // occurrences will be incremented on getting a value
                     occurrences: 0,
-                    values: Some(vec![]) 
+                    indices: vec![],
+                    values: Some(vec![]),
+                    default: false
                 });
             }
+            self.override_args(matches, v.name);
             if let Some(ref bl) = v.blacklist {
                 for name in bl {
                     self.blacklist.insert(name);
@@ -1399,7 +3937,7 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
                 // final required list
                 for n in reqs {
                     self.matched_reqs.insert(n);
-                    if matches.args.contains_key(n) { continue; }
+                    if self.arg_or_group_present(matches, n) { continue; }
 
                     self.required.insert(n);
                 }
@@ -1407,41 +3945,75 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
 
             parse_group_reqs!(self, v);
 
-            return Some(v.name)
-        } 
+            // An option whose value is optional doesn't consume the next argument
+            if v.optional_val() {
+                if let Some(ref mut o) = matches.args.get_mut(v.name) {
+                    o.occurrences += 1;
+                    if let (Some(ref mut vals), Some(d)) = (o.values.as_mut(), v.default_missing_val) {
+                        vals.push(OsString::from(d));
+                        o.indices.push(self.cur_idx);
+                    }
+                }
+                return None;
+            }
 
-        // Didn't match a flag or option, must be invalid
-        self.report_error( format!("Argument -{} isn't valid",arg_c), true, true);
+            return Some((v.name, v.num_vals.unwrap_or(1)))
+        } 
 
+        // The short was verified to be a flag or option above
         unreachable!();
     }
 
+    // Checks a short or cluster of shorts (without the leading '-') by the rules shared by the
+    // parser and the PartialParser: each short must be a flag, except for the last one which may
+    // also be an option taking the next argument as its value. Returns the offending short along
+    // with the error, which is still missing the hints only the parser can give
+    pub(crate) fn short_cluster_error(&self, cluster: &str) -> Option<(char, String)> {
+        let last = cluster.chars().count().saturating_sub(1);
+        for (i, c) in cluster.chars().enumerate() {
+            if self.flags.values().any(|f| f.short == Some(c)) { continue; }
+            if self.opts.values().any(|o| o.short == Some(c)) {
+                if i == last { continue; }
+                return Some((c, format!("Argument {} takes a value, so it can only be the last one in a cluster of shorts (found in {})",
+                    quote(&format!("-{}", c)[..]), quote(&format!("-{}", cluster)[..]))));
+            }
+            let found_in = if last > 0 { format!(" (found in {})", quote(&format!("-{}", cluster)[..])) } else { String::new() };
+            return Some((c, format!("Argument {} isn't valid{}", quote(&format!("-{}", c)[..]), found_in)));
+        }
+        None
+    }
+
     fn parse_single_short_flag(&mut self, matches: &mut ArgMatches<'ar, 'ar>, arg: char) -> bool {
         for v in self.flags.values().filter(|&v| v.short.is_some()).filter(|&v| v.short.unwrap() == arg) {
             // Ensure this flag isn't on the mutually excludes list
             if self.blacklist.contains(v.name) {
-                self.report_error(format!("The argument -{} cannot be used with one or more of the other specified arguments", arg),
+                self.report_error(format!("The argument {} cannot be used with one or more of the other specified arguments", disp_name!(v, format!("-{}", arg))),
                     true, true);
             }
 
             // Make sure this isn't one being added multiple times if it doesn't suppor it
             if matches.args.contains_key(v.name) && !v.multiple {
-                self.report_error(format!("Argument -{} was supplied more than once, but does not support multiple values", arg), true, true);
+                self.report_error(format!("Argument {} was supplied more than once, but does not support multiple values", disp_name!(v, format!("-{}", arg))), true, true);
             }
 
             let mut done = false;
             if let Some(ref mut f) = matches.args.get_mut(v.name) {
                 done = true;
                 f.occurrences = if v.multiple { f.occurrences + 1 } else { 1 };
+                f.indices.push(self.cur_idx);
             } 
             if !done {
                 matches.args.insert(v.name, MatchedArg{
                     // name: v.name.to_owned(),
                     occurrences: 1,
-                    values: None
+                    indices: vec![self.cur_idx],
+                    values: None,
+                    default: false
                 });
             }
 
+            self.override_args(matches, v.name);
+
             // If this flag was requierd, remove it
             // .. even though Flags shouldn't be required
             self.required.remove(v.name);
@@ -1458,7 +4030,7 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
             if let Some(ref reqs) = v.requires {
                 for n in reqs {
                     self.matched_reqs.insert(n);
-                    if matches.args.contains_key(n) { continue; }
+                    if self.arg_or_group_present(matches, n) { continue; }
 
                     self.required.insert(n);
                 }
@@ -1471,39 +4043,451 @@ impl<'a, 'v, 'ab, 'u, 'h, 'ar> App<'a, 'v, 'ab, 'u, 'h, 'ar>{
         false
     }
 
-    fn validate_blacklist(&self, matches: &ArgMatches<'ar, 'ar>) {
+    // The error message for an option which was supplied the wrong number of values
+    // Reports that the option ran out of arguments (or reached "--") while still requiring values
+    fn report_missing_vals(&self, name: &str, left: u8) {
+        if let Some(opt) = self.opts.get(name) {
+            if let Some(n) = opt.num_vals {
+                if n != left {
+                    self.report_error(self.num_vals_msg(opt, n as usize, (n - left) as usize), true, true);
+                }
+            }
+        }
+        let name = match self.opts.get(name) {
+            Some(opt) => disp_name!(opt, format!("\"{}\"", name)),
+            None      => format!("\"{}\"", name)
+        };
+        self.report_error(
+            format!("Argument {} requires a value but none was supplied", name),
+            true, true);
+    }
+
+    fn num_vals_msg(&self, opt: &OptBuilder, num_vals: usize, supplied: usize) -> String {
+        self.val_bounds_msg(&disp_name!(opt, opt.switch())[..], "", num_vals, supplied)
+    }
+
+    // The error message for an argument which was supplied a number of values outside of the
+    // bound, where bound is "", "at least ", or "at most "
+    fn val_bounds_msg(&self, arg: &str, bound: &str, num_vals: usize, supplied: usize) -> String {
+        format!("Argument {} requires {}{} value{}, but {} {} supplied",
+            arg, bound, num_vals, if num_vals == 1 { "" } else { "s" },
+            supplied, if supplied == 1 { "was" } else { "were" })
+    }
+
+    // Checks the total number of values supplied for each option and positional argument against
+    // its min_values() and max_values()
+    fn validate_num_vals(&self, matches: &ArgMatches<'ar, 'ar>) -> Result<(), String> {
+        for (name, ma) in matches.args.iter() {
+            let bounds = if let Some(o) = self.opts.get(name) {
+                Some((disp_name!(o, o.switch()), o.min_vals, o.max_vals))
+            } else {
+                self.positionals_idx.values()
+                                    .filter(|p| &p.name == name)
+                                    .next()
+                                    .map(|p| (disp_name!(p, format!("{}", p)), p.min_vals, p.max_vals))
+            };
+            if let Some((arg, min, max)) = bounds {
+                let supplied = ma.values.as_ref().map(|v| v.len()).unwrap_or(0);
+                if let Some(min) = min {
+                    if supplied < min as usize {
+                        return Err(self.val_bounds_msg(&arg[..], "at least ", min as usize, supplied));
+                    }
+                }
+                if let Some(max) = max {
+                    if supplied > max as usize {
+                        return Err(self.val_bounds_msg(&arg[..], "at most ", max as usize, supplied));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Returns a hint for an invalid short, suggesting a valid short of a flag or option which
+    // differs only by case or, in case the user forgot a hyphen, a long close to the entire
+    // cluster of shorts
+    fn short_hint(&self, arg: char, cluster: &str) -> String {
+        let lower = arg.to_lowercase().collect::<String>();
+        if let Some(s) = self.flags.values().filter_map(|f| f.short)
+                             .chain(self.opts.values().filter_map(|o| o.short))
+                             .filter(|&s| s != arg && s.to_lowercase().collect::<String>() == lower)
+                             .next() {
+            return format!("\n\n\tDid you mean -{}?", s);
+        }
+        if cluster.chars().count() > 2 {
+            let longs = self.flags.values().filter_map(|f| f.long).chain(self.opts.values().filter_map(|o| o.long));
+            if let Some(l) = did_you_mean(cluster, longs) {
+                return format!("\n\n\tDid you mean --{}?", l);
+            }
+        }
+        String::new()
+    }
+
+    // Hints at the accepted syntax when a separate value starts with '=', i.e. "--opt =value",
+    // which is most likely a mix of "--opt=value" and "--opt value"
+    fn equals_hint(&self, opt: &OptBuilder, val: &str) -> String {
+        if !val.starts_with('=') || val.len() == 1 {
+            return String::new();
+        }
+        let val = &val[1..];
+        match opt.long {
+            Some(l) => format!("\n\n\tThere must be no space before '=', did you mean {} or {}?",
+                quote(&format!("--{}={}", l, val)[..]), quote(&format!("--{} {}", l, val)[..])),
+            None    => format!("\n\n\tDid you mean {}?", quote(&format!("{} {}", opt.switch(), val)[..]))
+        }
+    }
+
+    // If a positional argument could still accept a value, returns a hint telling the user how
+    // to pass a value which starts with a hyphen (i.e. after a "--")
+    fn positional_hint(&self, matches: &ArgMatches<'ar, 'ar>, arg: &str) -> String {
+        if self.positionals_idx.values().any(|p| p.multiple || !matches.args.contains_key(p.name)) {
+            format!("\n\n\tIf you meant to supply {0} as a positional value, use -- {0} to stop parsing \
+                    flags and options", quote(arg))
+        } else {
+            String::new()
+        }
+    }
+
+    // Hints that a value which isn't valid is the name of a subcommand that was already used to
+    // get here, i.e. "build" in "prog build build", since it was most likely repeated by accident
+    fn repeated_subcommand_hint(&self, arg: &str) -> String {
+        if self.subcmd_path.iter().any(|n| n == arg) {
+            format!("\n\n\tThe subcommand {} was already used ({}), so it doesn't need to be repeated",
+                quote(arg), self.bin_name.clone().unwrap_or(self.name.clone()))
+        } else {
+            String::new()
+        }
+    }
+
+    // Reports an error suggesting the closest (visible) subcommand if an unexpected positional
+    // value is most likely a misspelled subcommand, i.e. "isntall" instead of "install"
+    fn check_subcommand_typo(&self, arg: &str) {
+        let names = self.subcommands.values().filter(|sc| !sc.hidden).map(|sc| &sc.name[..]);
+        if let Some(name) = did_you_mean(arg, names) {
+            let bin_name = self.bin_name.clone().unwrap_or(self.name.clone());
+            let help = if self.subcommands.contains_key("help") {
+                format!("\n\n\tTo see all available subcommands, try {}", quote(&format!("{} help", bin_name)[..]))
+            } else {
+                String::new()
+            };
+            self.report_error(format!("The subcommand {} wasn't recognized\n\n\tDid you mean {}?{}{}",
+                quote(arg), quote(name), help, self.repeated_subcommand_hint(arg)), true, true);
+        }
+    }
+
+    // Uses the default value for each option and positional argument which wasn't supplied
+    // Adds the values of the environment variables of options which weren't used on the command
+    // line, which then go through the same validation as values from the command line
+    fn add_env(&mut self, matches: &mut ArgMatches<'ar, 'ar>) {
+        let mut used = vec![];
+        for o in self.opts.values() {
+            let var = match o.env {
+                Some(var) if !matches.args.contains_key(o.name) => var,
+                _                                               => continue
+            };
+            let val = match env::var_os(var) {
+                Some(val) => val,
+                None      => continue
+            };
+            if val.is_empty() { continue; }
+            let vals = o.split_vals(&val);
+            if let Some(ref p_vals) = o.possible_vals {
+                if let Some(v) = vals.iter().filter(|v| v.to_str().map_or(true, |v| !p_vals.contains(v))).next() {
+                    if let Some(msg) = o.error_message {
+                        self.report_error(msg.to_owned(), true, true);
+                    }
+                    self.report_error(format!("{} isn't a valid value for {}{}", quote(&v.to_string_lossy()[..]), disp_name!(o, o.switch()),
+                        format!("\n    [valid values:{}]", p_vals.iter().fold(String::new(), |acc, name| acc + &format!(" {}",name)[..] )) ), true, true);
+                }
+            }
+            matches.args.insert(o.name, MatchedArg{
+                occurrences: 0,
+                indices: vec![],
+                values: Some(vals),
+                default: false
+            });
+            used.push(o.name);
+        }
+        for name in used {
+            let (bl, reqs) = {
+                let o = self.opts.get(name).unwrap();
+                (o.blacklist.clone(), o.requires.clone())
+            };
+            if let Some(bl) = bl {
+                for n in bl {
+                    self.blacklist.insert(n);
+                    self.required.remove(n);
+                }
+            }
+            self.required.remove(name);
+            if let Some(reqs) = reqs {
+                for n in reqs {
+                    self.matched_reqs.insert(n);
+                    if self.arg_or_group_present(matches, n) { continue; }
+
+                    self.required.insert(n);
+                }
+            }
+        }
+    }
+
+    fn add_defaults(&mut self, matches: &mut ArgMatches<'ar, 'ar>) {
+        // Conditional defaults are resolved first, so they only depend on the supplied arguments
+        let mut cond_defaults = vec![];
+        for (name, ifs) in self.opts.values().map(|o| (o.name, &o.default_val_ifs))
+                               .chain(self.positionals_idx.values().map(|p| (p.name, &p.default_val_ifs))) {
+            if matches.args.contains_key(name) { continue; }
+            if let Some(ref ifs) = *ifs {
+                let dv = ifs.iter().filter(|&&(a, v, _)| match matches.args.get(a) {
+                    Some(ma) => v.map_or(true, |v| ma.values.as_ref().map_or(false, |vals| vals.iter().any(|mv| *mv == *v))),
+                    None     => false
+                }).map(|&(_, _, dv)| dv).next();
+                if let Some(dv) = dv {
+                    cond_defaults.push((name, dv));
+                }
+            }
+        }
+        for (name, dv) in cond_defaults {
+            matches.args.insert(name, MatchedArg{
+                occurrences: 0,
+                indices: vec![],
+                values: Some(vec![OsString::from(dv)]),
+                default: true
+            });
+            self.required.remove(name);
+        }
+        for o in self.opts.values() {
+            if let Some(dv) = o.default_val {
+                if matches.args.contains_key(o.name) { continue; }
+                matches.args.insert(o.name, MatchedArg{
+                    occurrences: 0,
+                    indices: vec![],
+                    values: Some(vec![OsString::from(dv)]),
+                    default: true
+                });
+                self.required.remove(o.name);
+            }
+        }
+        for p in self.positionals_idx.values() {
+            if let Some(dv) = p.default_val {
+                if matches.args.contains_key(p.name) { continue; }
+                matches.args.insert(p.name, MatchedArg{
+                    occurrences: 0,
+                    indices: vec![],
+                    values: Some(vec![OsString::from(dv)]),
+                    default: true
+                });
+                self.required.remove(p.name);
+            }
+        }
+    }
+
+    // Displays a warning on stderr for each deprecated argument which was used
+    fn warn_deprecated(&self, matches: &ArgMatches<'ar, 'ar>) {
+        for name in matches.args.keys() {
+            let dep = if let Some(f) = self.flags.get(name) {
+                f.deprecated.map(|d| (format!("{}", f), d))
+            } else if let Some(o) = self.opts.get(name) {
+                o.deprecated.map(|d| (format!("{}", o), d))
+            } else {
+                self.positionals_idx.values()
+                                    .filter(|p| &p.name == name)
+                                    .next()
+                                    .and_then(|p| p.deprecated.map(|d| (format!("{}", p), d)))
+            };
+            if let Some((arg, note)) = dep {
+                self.write_error_output(&format!("warning: the argument {} is deprecated{}{}\n", arg,
+                    if note.is_empty() { "" } else { ": " }, note)[..]);
+            }
+        }
+    }
+
+    // Checks whether the argument with the given name, or if the name belongs to a group any
+    // member of that group, was used
+    fn arg_or_group_present(&self, matches: &ArgMatches<'ar, 'ar>, name: &str) -> bool {
+        if matches.args.contains_key(name) {
+            return true;
+        }
+        match self.groups.get(name) {
+            Some(grp) => grp.args.iter().any(|n| matches.args.contains_key(n)),
+            None      => false
+        }
+    }
+
+    // Whether negative numbers are allowed as values and the argument is one, i.e. -5 or -0.3
+    fn is_neg_num(&self, arg: &str) -> bool {
+        self.allow_neg_nums &&
+            arg.starts_with("-") &&
+            arg[1..].starts_with(|c: char| c.is_digit(10) || c == '.') &&
+            arg[1..].parse::<f64>().is_ok()
+    }
+
+    // Whether an argument starting with '-' is `--` or a valid flag or option (by its long, one of
+    // its aliases, or its short for the first char of a short cluster)
+    fn is_known_arg(&self, arg: &str) -> bool {
+        if arg == "--" {
+            return true;
+        }
+        if arg.starts_with("--") {
+            let long = arg[2..].splitn(2, '=').next().unwrap_or("");
+            self.flags.values().any(|f| f.long == Some(long) || has_alias!(f, long)) ||
+                self.opts.values().any(|o| o.long == Some(long) || has_alias!(o, long))
+        } else {
+            match arg.chars().nth(1) {
+                Some(c) => self.flags.values().any(|f| f.short == Some(c)) || self.opts.values().any(|o| o.short == Some(c)),
+                None    => false
+            }
+        }
+    }
+
+    // Removes the arguments overridden by (or overriding) the argument which was just used from
+    // the matches, so whichever is used last wins
+    fn override_args(&self, matches: &mut ArgMatches<'ar, 'ar>, name: &str) {
+        let overridden = matches.args.keys()
+                                     .filter(|&&n| n != name && (self.overrides(name, n) || self.overrides(n, name)))
+                                     .map(|&n| n)
+                                     .collect::<Vec<_>>();
+        for n in overridden {
+            matches.args.remove(n);
+        }
+    }
+
+    // Whether the argument a overrides the argument b
+    fn overrides(&self, a: &str, b: &str) -> bool {
+        let overrides = if let Some(f) = self.flags.get(a) {
+            &f.overrides
+        } else if let Some(o) = self.opts.get(a) {
+            &o.overrides
+        } else if let Some(p) = self.positionals_idx.values().filter(|p| p.name == a).next() {
+            &p.overrides
+        } else {
+            return false;
+        };
+        overrides.as_ref().map_or(false, |o| o.contains(&b))
+    }
+
+    fn validate_blacklist(&self, matches: &ArgMatches<'ar, 'ar>) -> Result<(), String> {
         for name in self.blacklist.iter() {
             if matches.args.contains_key(name) {
-                self.report_error(format!("The argument {} cannot be used with one or more of the other specified arguments",
+                return Err(format!("The argument {} cannot be used with one or more of the other specified arguments",
                     if let Some(ref flag) = self.flags.get(name) {
-                        format!("{}", flag)
+                        disp_name!(flag, format!("{}", flag))
                     } else if let Some(ref opt) = self.opts.get(name) {
-                        format!("{}", opt)
+                        disp_name!(opt, format!("{}", opt))
                     } else {
                         match self.positionals_idx.values().filter(|p| p.name == *name).next() {
-                            Some(pos) => format!("{}", pos),
+                            Some(pos) => disp_name!(pos, format!("{}", pos)),
                             None      => format!("\"{}\"", name)
                         }
-                    }), true, true);
+                    }));
             } else if self.groups.contains_key(name) {
                 let grp = self.groups.get(name).unwrap();
                 for n in grp.args.iter() {
                     if matches.args.contains_key(n) {
-                        self.report_error(format!("The argument {} cannot be used with one or more of the other specified arguments",
+                        return Err(format!("The argument {} cannot be used with one or more of the other specified arguments",
                             if let Some(ref flag) = self.flags.get(n) {
-                                format!("{}", flag)
+                                disp_name!(flag, format!("{}", flag))
                             } else if let Some(ref opt) = self.opts.get(n) {
-                                format!("{}", opt)
+                                disp_name!(opt, format!("{}", opt))
                             } else {
                                 match self.positionals_idx.values().filter(|p| p.name == *name).next() {
-                                    Some(pos) => format!("{}", pos),
+                                    Some(pos) => disp_name!(pos, format!("{}", pos)),
                                     None      => format!("\"{}\"", n)
                                 }
-                            }), true, true);
+                            }));
                     }
                 } 
             }
         }
+        Ok(())
+    }
+
+    // Builds the error message for missing required arguments, using any custom messages the
+    // missing arguments define in place of the generic message
+    fn missing_required_msg(&self) -> String {
+        let mut msgs = vec![];
+        let mut missing = vec![];
+        // The required arguments are stored unordered, so they're sorted for a stable message
+        let mut names = self.required.iter().collect::<Vec<_>>();
+        names.sort();
+        for name in names {
+            let (msg, usage, help) = if let Some(f) = self.flags.get(name) {
+                (f.error_message, disp_name!(f, format!("{}", f)), f.help)
+            } else if let Some(o) = self.opts.get(name) {
+                (o.error_message, disp_name!(o, format!("{}", o)), o.help)
+            } else if let Some(p) = self.positionals_idx.values().filter(|p| &p.name == name).next() {
+                (p.error_message, disp_name!(p, format!("<{}>{}", p.usage_name(), if p.multiple { "..." } else { "" })), p.help)
+            } else if let Some(g) = self.groups.get(name) {
+                let mut args = g.args.iter().map(|n| self.group_arg_usage(n)).collect::<Vec<_>>();
+                args.sort();
+                (None, format!("<{}>", args.join("|")), None)
+            } else {
+                (None, name.to_string(), None)
+            };
+            match msg {
+                Some(m) => msgs.push(m.to_owned()),
+                None    => missing.push((usage, help))
+            }
+        }
+        if !missing.is_empty() {
+            msgs.push("The following required arguments were not supplied:".to_owned());
+        } else if msgs.is_empty() {
+            msgs.push("One or more required arguments were not supplied".to_owned());
+        }
+        // Each missing argument is listed with its value placeholders and the first line of its
+        // help, so the error alone is enough to fix the invocation
+        let width = missing.iter().map(|&(ref u, _)| u.len()).max().unwrap_or(0);
+        for (usage, help) in missing {
+            let line = format!("    {:w$}    {}", usage, help.and_then(|h| h.lines().next()).unwrap_or(""), w = width);
+            msgs.push(line.trim_right().to_owned());
+        }
+        msgs.join("\n")
+    }
+
+    // Removes the arguments which are only required unless other arguments were used from the
+    // required list, if those other arguments were used
+    fn validate_required_unless(&mut self, matches: &ArgMatches<'ar, 'ar>) {
+        let unless = self.opts.values().map(|o| (o.name, &o.r_unless, o.r_unless_all))
+                         .chain(self.positionals_idx.values().map(|p| (p.name, &p.r_unless, p.r_unless_all)))
+                         .filter_map(|(name, r_unless, all)| r_unless.as_ref().map(|ns| (name, ns, all)))
+                         .filter(|&(_, ns, all)| if all {
+                             ns.iter().all(|n| self.arg_or_group_present(matches, n))
+                         } else {
+                             ns.iter().any(|n| self.arg_or_group_present(matches, n))
+                         })
+                         .map(|(name, _, _)| name)
+                         .collect::<Vec<_>>();
+        for name in unless {
+            self.required.remove(name);
+        }
+    }
+
+    // Adds the arguments which are required because another argument was used with a specific
+    // value to the required list
+    fn validate_required_if(&mut self, matches: &ArgMatches<'ar, 'ar>) {
+        let has_val = |name: &str, val: &str| matches.args.get(name)
+                                                      .and_then(|ma| ma.values.as_ref())
+                                                      .map(|vals| vals.iter().any(|v| v == val))
+                                                      .unwrap_or(false);
+        let mut reqs = vec![];
+        for (name, r_ifs, requires_ifs) in self.opts.values().map(|o| (o.name, &o.r_ifs, &o.requires_ifs))
+                                               .chain(self.positionals_idx.values().map(|p| (p.name, &p.r_ifs, &p.requires_ifs))) {
+            if let Some(ref r_ifs) = *r_ifs {
+                if !matches.args.contains_key(name) && r_ifs.iter().any(|&(a, v)| has_val(a, v)) {
+                    reqs.push(name);
+                }
+            }
+            if let Some(ref requires_ifs) = *requires_ifs {
+                for &(v, a) in requires_ifs.iter() {
+                    if has_val(name, v) && !self.arg_or_group_present(matches, a) {
+                        reqs.push(a);
+                    }
+                }
+            }
+        }
+        for name in reqs {
+            self.required.insert(name);
+        }
     }
 
     fn validate_required(&self, matches: &ArgMatches<'ar, 'ar>) -> bool{
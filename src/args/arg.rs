@@ -1,4 +1,68 @@
+#[cfg(feature = "yaml")]
+use yaml_rust::Yaml;
+
 use usageparser::{UsageParser, UsageToken};
+use std::str::FromStr;
+use std::ffi::OsStr;
+
+use args::ValueHint;
+
+/// Behavioral toggles of an `Arg`, set with `Arg::setting()` and inspected with `Arg::is_set()`,
+/// as an alternative to the builder method of each setting (i.e. `Arg::required()`), which is
+/// useful when arguments are configured programmatically. Each setting is off by default.
+///
+///
+/// # Example
+///
+/// ```no_run
+/// # use clap::{App, Arg, ArgSettings};
+/// let arg = Arg::with_name("config")
+///               .long("config")
+///               .setting(ArgSettings::TakesValue)
+///               .setting(ArgSettings::Required);
+/// assert!(arg.is_set(ArgSettings::Required));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArgSettings {
+    /// The argument must be present at runtime, see `Arg::required()`
+    Required,
+    /// The argument takes a value, see `Arg::takes_value()`
+    TakesValue,
+    /// The argument may be used more than once, see `Arg::multiple()`
+    Multiple,
+    /// The option consumes all following values, see `Arg::multiple_values()`
+    MultipleValues,
+    /// Values starting with a `-` are accepted, see `Arg::allow_hyphen_values()`
+    AllowHyphenValues,
+    /// The positional argument is only populated after `--`, see `Arg::last()`
+    Last,
+    /// The argument is also available to all subcommands, see `Arg::global()`
+    Global,
+    /// Multiple values must be separated by the delimiter, see `Arg::require_delimiter()`
+    RequireDelimiter,
+    /// The argument is omitted from the help information, see `Arg::hidden()`
+    Hidden
+}
+
+impl FromStr for ArgSettings {
+    type Err = String;
+
+    // The names are matched case insensitively, and may be written in snake_case (i.e. in YAML)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.replace("_", "").to_lowercase()[..] {
+            "required"          => Ok(ArgSettings::Required),
+            "takesvalue"        => Ok(ArgSettings::TakesValue),
+            "multiple"          => Ok(ArgSettings::Multiple),
+            "multiplevalues"    => Ok(ArgSettings::MultipleValues),
+            "allowhyphenvalues" => Ok(ArgSettings::AllowHyphenValues),
+            "last"              => Ok(ArgSettings::Last),
+            "global"            => Ok(ArgSettings::Global),
+            "requiredelimiter"  => Ok(ArgSettings::RequireDelimiter),
+            "hidden"            => Ok(ArgSettings::Hidden),
+            _                   => Err(format!("{} isn't a valid setting", s))
+        }
+    }
+}
 
 /// The abstract representation of a command line argument used by the consumer of the library.
 /// Used to set all the options and relationships that define a valid argument for the program.
@@ -28,6 +92,7 @@ use usageparser::{UsageParser, UsageToken};
 /// // Using a usage string (setting a similar argument to the one above)
 /// Arg::from_usage("-i --input=[input] 'Provides an input file to the program'")
 /// # ).get_matches();
+#[derive(Clone)]
 pub struct Arg<'n, 'l, 'h, 'g, 'p, 'r> {
     /// The unique name of the argument, required
     #[doc(hidden)]
@@ -40,10 +105,17 @@ pub struct Arg<'n, 'l, 'h, 'g, 'p, 'r> {
     /// **NOTE:** `long` is mutually exclusive with `index`
     #[doc(hidden)]
     pub long: Option<&'l str>,
+    /// Alternate long versions of the argument, along with whether each is visible in the
+    /// usage and help information
+    #[doc(hidden)]
+    pub aliases: Option<Vec<(&'l str, bool)>>,
     /// The string of text that will displayed to the user when the application's
     /// `help` text is displayed
     #[doc(hidden)]
     pub help: Option<&'h str>,
+    /// The detailed help text displayed instead of `help` when the user requests `--help`
+    #[doc(hidden)]
+    pub long_help: Option<&'h str>,
     /// If this is a required by default when using the command line program
     /// i.e. a configuration file that's required for the program to function
     /// **NOTE:** required by default means, it is required *until* mutually
@@ -63,19 +135,113 @@ pub struct Arg<'n, 'l, 'h, 'g, 'p, 'r> {
     /// I.e. `-v -v -v` or `-vvv`
     #[doc(hidden)]
     pub multiple: bool,
+    /// Determines if an option consumes all following values (until the next argument starting
+    /// with a `-`) instead of only one each time it's used
+    #[doc(hidden)]
+    pub multiple_vals: bool,
+    /// Determines if values starting with a `-` are accepted where they'd otherwise be taken for
+    /// an (unknown) flag or option
+    #[doc(hidden)]
+    pub allow_hyphen_vals: bool,
+    /// Determines if an option consuming values takes `--` as a value, instead of ending its values
+    #[doc(hidden)]
+    pub sep_as_val: bool,
+    /// Determines if this positional argument is only populated from the arguments following `--`
+    #[doc(hidden)]
+    pub last: bool,
+    /// Determines if this argument is also available to all subcommands (recursively)
+    #[doc(hidden)]
+    pub global: bool,
     /// A list of names for other arguments that *may not* be used with this flag
     #[doc(hidden)]
     pub blacklist: Option<Vec<&'r str>>, 
+    /// The only arguments which *may* be used with this argument
+    #[doc(hidden)]
+    pub conflicts_except: Option<Vec<&'r str>>,
+    /// A list of names for other arguments which this argument overrides, whichever is used
+    /// last wins
+    #[doc(hidden)]
+    pub overrides: Option<Vec<&'r str>>,
     /// A list of possible values for an option or positional argument
     #[doc(hidden)]
     pub possible_vals: Option<Vec<&'p str>>,
+    /// The value used when an option or positional argument isn't supplied at runtime
+    #[doc(hidden)]
+    pub default_val: Option<&'p OsStr>,
+    /// A list of other arguments, optional values, and the value used when this argument isn't
+    /// supplied but the other argument was (with the value)
+    #[doc(hidden)]
+    pub default_val_ifs: Option<Vec<(&'r str, Option<&'p str>, &'p str)>>,
+    /// The value used when an option whose value is optional is used without one
+    #[doc(hidden)]
+    pub default_missing_val: Option<&'p str>,
+    /// The environment variable an option's value is read from if it isn't used
+    #[doc(hidden)]
+    pub env: Option<&'p str>,
+    /// The character which separates multiple values within a single value of an option
+    #[doc(hidden)]
+    pub val_delim: Option<char>,
+    /// Whether values are split at the delimiter, `None` meaning only when `multiple` is set
+    #[doc(hidden)]
+    pub use_delim: Option<bool>,
+    /// Whether multiple values must be supplied delimited, instead of by repeating the option
+    #[doc(hidden)]
+    pub req_delim: bool,
+    /// The exact number of values an option requires each time it's used
+    #[doc(hidden)]
+    pub num_vals: Option<u8>,
+    /// The names of the values of an option displayed in the usage and help information
+    #[doc(hidden)]
+    pub val_names: Option<Vec<&'n str>>,
+    /// The kind of value the argument accepts, used by shell completions
+    #[doc(hidden)]
+    pub value_hint: Option<ValueHint>,
+    /// A function listing the candidate values at runtime, used by shell completions
+    #[doc(hidden)]
+    pub candidates: Option<fn() -> Vec<String>>,
+    /// The minimum number of values an option or positional argument requires in total
+    #[doc(hidden)]
+    pub min_vals: Option<u8>,
+    /// The maximum number of values an option or positional argument accepts in total
+    #[doc(hidden)]
+    pub max_vals: Option<u8>,
     /// A list of names of other arguments that are *required* to be used when 
     /// this flag is used
     #[doc(hidden)]
     pub requires: Option<Vec<&'r str>>,
+    /// A list of names of other arguments which, when used, make this argument no longer
+    /// required
+    #[doc(hidden)]
+    pub r_unless: Option<Vec<&'r str>>,
+    /// Whether *all* of the arguments in `r_unless` must be used, instead of any of them
+    #[doc(hidden)]
+    pub r_unless_all: bool,
+    /// A list of other arguments and values which make this argument required, when any of the
+    /// arguments was used with its value
+    #[doc(hidden)]
+    pub r_ifs: Option<Vec<(&'r str, &'p str)>>,
+    /// A list of values and other arguments which are required when this argument is used with
+    /// the value
+    #[doc(hidden)]
+    pub requires_ifs: Option<Vec<(&'p str, &'r str)>>,
     /// A name of the group the argument belongs to
     #[doc(hidden)]
-    pub group: Option<&'g str>
+    pub group: Option<&'g str>,
+    /// A custom message displayed when this argument is missing or has an invalid value
+    #[doc(hidden)]
+    pub error_message: Option<&'h str>,
+    /// The spelling of the argument used in error messages
+    #[doc(hidden)]
+    pub disp_name: Option<&'h str>,
+    /// A note on what to use instead, if the argument is deprecated
+    #[doc(hidden)]
+    pub deprecated: Option<&'h str>,
+    /// Whether the argument is omitted from the help information
+    #[doc(hidden)]
+    pub hidden: bool,
+    /// The heading of the help section the argument is listed in, instead of its default one
+    #[doc(hidden)]
+    pub help_heading: Option<&'h str>
 }
 
 impl<'n, 'l, 'h, 'g, 'p, 'r> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
@@ -109,11 +275,42 @@ impl<'n, 'l, 'h, 'g, 'p, 'r> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
             required: false,
             takes_value: false,
             multiple: false,
+            multiple_vals: false,
+            allow_hyphen_vals: false,
+            sep_as_val: false,
+            last: false,
+            global: false,
             index: None,
             possible_vals: None,
+            default_val: None,
+            default_val_ifs: None,
+            default_missing_val: None,
+            env: None,
+            val_delim: None,
+            use_delim: None,
+            req_delim: false,
+            num_vals: None,
+            val_names: None,
+            value_hint: None,
+            candidates: None,
+            min_vals: None,
+            max_vals: None,
             blacklist: None,
+            conflicts_except: None,
+            overrides: None,
             requires: None,
+            r_unless: None,
+            r_unless_all: false,
+            r_ifs: None,
+            requires_ifs: None,
             group: None,
+            error_message: None,
+            disp_name: None,
+            long_help: None,
+            aliases: None,
+            deprecated: None,
+            hidden: false,
+            help_heading: None,
         }
     }
 
@@ -144,11 +341,42 @@ impl<'n, 'l, 'h, 'g, 'p, 'r> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
             required: false,
             takes_value: false,
             multiple: false,
+            multiple_vals: false,
+            allow_hyphen_vals: false,
+            sep_as_val: false,
+            last: false,
+            global: false,
             index: None,
             possible_vals: None,
+            default_val: None,
+            default_val_ifs: None,
+            default_missing_val: None,
+            env: None,
+            val_delim: None,
+            use_delim: None,
+            req_delim: false,
+            num_vals: None,
+            val_names: None,
+            value_hint: None,
+            candidates: None,
+            min_vals: None,
+            max_vals: None,
             blacklist: None,
+            conflicts_except: None,
+            overrides: None,
             requires: None,
+            r_unless: None,
+            r_unless_all: false,
+            r_ifs: None,
+            requires_ifs: None,
             group: None,
+            error_message: None,
+            disp_name: None,
+            long_help: None,
+            aliases: None,
+            deprecated: None,
+            hidden: false,
+            help_heading: None,
         }
     }
 
@@ -259,12 +487,150 @@ impl<'n, 'l, 'h, 'g, 'p, 'r> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
             required: required,
             takes_value: takes_value,
             multiple: multiple,
+            multiple_vals: false,
+            allow_hyphen_vals: false,
+            sep_as_val: false,
+            last: false,
+            global: false,
             index: None,
             possible_vals: None,
+            default_val: None,
+            default_val_ifs: None,
+            default_missing_val: None,
+            env: None,
+            val_delim: None,
+            use_delim: None,
+            req_delim: false,
+            num_vals: None,
+            val_names: None,
+            value_hint: None,
+            candidates: None,
+            min_vals: None,
+            max_vals: None,
             blacklist: None,
+            conflicts_except: None,
+            overrides: None,
             requires: None,
+            r_unless: None,
+            r_unless_all: false,
+            r_ifs: None,
+            requires_ifs: None,
             group: None,
+            error_message: None,
+            disp_name: None,
+            long_help: None,
+            aliases: None,
+            deprecated: None,
+            hidden: false,
+            help_heading: None,
+        }
+    }
+
+    /// Creates a new instance of `Arg` from a YAML hash with a single key, the name of the
+    /// argument, whose value is a hash of the argument's settings. The settings use the same names
+    /// as the builder methods (i.e. `short`, `long`, `alias`, `visible_alias`, `help`, `long_help`,
+    /// `required`, `takes_value`, `index`, `multiple`, `multiple_values`, `multiple_occurrences`,
+    /// `allow_hyphen_values`, `last`, `global`, `group`, `possible_values`, `default_value`,
+    /// `default_missing_value`, `env`, `value_delimiter`, `use_delimiter`, `require_delimiter`,
+    /// `number_of_values`, `value_name`, `value_names`, `value_hint`, `min_values`, `max_values`,
+    /// `required_unless`, `required_unless_any`, `required_unless_all`, `required_if_eq`,
+    /// `requires`, `requires_if`, `conflicts_with`, `conflicts_with_everything_except`,
+    /// `overrides_with`, `error_message`, `display_name`, `deprecated`, `hidden`, and `settings`).
+    /// `settings` is a list of `ArgSettings` names (i.e. `takes_value`). The pair of `required_if_eq` and `requires_if` is given
+    /// as a list of two strings, and `value_hint` as the name of a `ValueHint` (i.e. `file_path`).
+    ///
+    /// **NOTE:** This requires the `yaml` cargo feature
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # use clap::{App, Arg};
+    /// // In arg.yml:
+    /// //
+    /// // config:
+    /// //     short: c
+    /// //     long: config
+    /// //     takes_value: true
+    /// //     help: Sets a custom config file
+    /// let yml = load_yaml!("arg.yml");
+    /// let arg = Arg::from_yaml(yml);
+    /// ```
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(y: &'n Yaml) -> Arg<'n, 'n, 'n, 'n, 'n, 'n> {
+        let (name, settings) = match y.as_hash().and_then(|h| if h.len() == 1 { h.iter().next() } else { None }) {
+            Some((k, v)) => (k.as_str().expect("Arg name in YAML must be a string"), v),
+            None         => panic!("Arg in YAML must be a hash with a single key (the name of the argument)")
+        };
+        let mut a = Arg::with_name(name);
+        let settings = match settings.as_hash() {
+            Some(h) => h,
+            None    => return a
+        };
+        for (k, v) in settings.iter() {
+            let k = k.as_str().expect("Arg setting names in YAML must be strings");
+            let s = v.as_str();
+            let b = v.as_bool();
+            let l = || v.as_vec()
+                        .unwrap_or_else(|| panic!("Arg \"{}\" YAML setting \"{}\" must be a list", name, k))
+                        .iter()
+                        .map(|i| i.as_str().unwrap_or_else(|| panic!("Arg \"{}\" YAML setting \"{}\" must be a list of strings", name, k)))
+                        .collect::<Vec<_>>();
+            a = match (k, s, b) {
+                ("short", Some(s), _)                => a.short(s),
+                ("long", Some(s), _)                 => a.long(s),
+                ("alias", Some(s), _)                => a.alias(s),
+                ("visible_alias", Some(s), _)        => a.visible_alias(s),
+                ("help", Some(s), _)                 => a.help(s),
+                ("long_help", Some(s), _)            => a.long_help(s),
+                ("group", Some(s), _)                => a.group(s),
+                ("error_message", Some(s), _)        => a.error_message(s),
+                ("display_name", Some(s), _)         => a.display_name(s),
+                ("deprecated", Some(s), _)           => a.deprecated(s),
+                ("help_heading", Some(s), _)         => a.help_heading(s),
+                ("required", _, Some(b))             => a.required(b),
+                ("takes_value", _, Some(b))          => a.takes_value(b),
+                ("multiple", _, Some(b))             => a.multiple(b),
+                ("multiple_values", _, Some(b))      => a.multiple_values(b),
+                ("multiple_occurrences", _, Some(b)) => a.multiple_occurrences(b),
+                ("allow_hyphen_values", _, Some(b))  => a.allow_hyphen_values(b),
+                ("separator_as_value", _, Some(b))   => a.separator_as_value(b),
+                ("last", _, Some(b))                 => a.last(b),
+                ("global", _, Some(b))               => a.global(b),
+                ("hidden", _, Some(b))               => a.hidden(b),
+                ("settings", _, _)                   => {
+                    for st in l() {
+                        a = a.setting(st.parse().unwrap_or_else(|e| panic!("Arg \"{}\" YAML setting \"settings\" is invalid: {}", name, e)));
+                    }
+                    a
+                },
+                ("index", _, _)                      => a.index(v.as_i64().unwrap_or_else(|| panic!("Arg \"{}\" YAML setting \"index\" must be a number", name)) as u8),
+                ("possible_values", _, _)            => a.possible_values(l()),
+                ("default_value", Some(s), _)        => a.default_value(s),
+                ("default_missing_value", Some(s), _) => a.default_missing_value(s),
+                ("env", Some(s), _)                  => a.env(s),
+                ("value_delimiter", Some(s), _) if s.chars().count() == 1 => a.value_delimiter(s.chars().next().unwrap()),
+                ("use_delimiter", _, Some(b))        => a.use_delimiter(b),
+                ("require_delimiter", _, Some(b))    => a.require_delimiter(b),
+                ("value_name", Some(s), _)           => a.value_name(s),
+                ("value_names", _, _)                => a.value_names(l()),
+                ("value_hint", Some(s), _)           => a.value_hint(s.parse().unwrap_or_else(|e| panic!("Arg \"{}\" YAML setting \"value_hint\" is invalid: {}", name, e))),
+                ("number_of_values", _, _)           => a.number_of_values(v.as_i64().unwrap_or_else(|| panic!("Arg \"{}\" YAML setting \"number_of_values\" must be a number", name)) as u8),
+                ("min_values", _, _)                 => a.min_values(v.as_i64().unwrap_or_else(|| panic!("Arg \"{}\" YAML setting \"min_values\" must be a number", name)) as u8),
+                ("max_values", _, _)                 => a.max_values(v.as_i64().unwrap_or_else(|| panic!("Arg \"{}\" YAML setting \"max_values\" must be a number", name)) as u8),
+                ("required_unless", Some(s), _)      => a.required_unless(s),
+                ("required_unless_any", _, _)        => a.required_unless_any(l()),
+                ("required_unless_all", _, _)        => a.required_unless_all(l()),
+                ("required_if_eq", _, _)             => { let p = l(); if p.len() != 2 { panic!("Arg \"{}\" YAML setting \"required_if_eq\" must be a list of an argument and a value", name) } a.required_if_eq(p[0], p[1]) },
+                ("requires_if", _, _)                => { let p = l(); if p.len() != 2 { panic!("Arg \"{}\" YAML setting \"requires_if\" must be a list of a value and an argument", name) } a.requires_if(p[0], p[1]) },
+                ("requires", _, _)                   => a.requires_all(l()),
+                ("conflicts_with", _, _)             => a.conflicts_with_all(l()),
+                ("conflicts_with_everything_except", _, _) => a.conflicts_with_everything_except(l()),
+                ("overrides_with", _, _)             => a.overrides_with_all(l()),
+                _                                    => panic!("Unknown or invalid setting \"{}\" for arg \"{}\" in YAML", k, name)
+            };
         }
+        a
     }
 
     /// Sets the short version of the argument without the preceding `-`.
@@ -275,7 +641,9 @@ impl<'n, 'l, 'h, 'g, 'p, 'r> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
     /// will not asign those to the displaying of version or help.
     ///
     /// **NOTE:** Any leading `-` characters will be stripped, and only the first
-    /// non `-` chacter will be used as the `short` version
+    /// non `-` chacter will be used as the `short` version. Any unicode character (not only
+    /// ASCII) may be used, i.e. `-ä`, however characters made up of multiple code points (such as
+    /// a letter followed by a combining accent) can't be used as a `short`.
     ///
     ///
     /// # Example
@@ -297,7 +665,9 @@ impl<'n, 'l, 'h, 'g, 'p, 'r> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
     /// By default `clap` automatically assigns `version` and `help` to display version and help information 
     /// respectivly. You may use `version` or `help` for your own purposes, in which case `clap` simply
     /// will not asign those to the displaying of version or help automatically, and you will have to do
-    /// so manually.
+    /// so manually. In debug builds a warning is displayed when this happens, unless the argument is
+    /// named `help` or `version` as well, or the flag was disabled with `AppSettings::DisableHelpFlag`
+    /// (or `AppSettings::DisableVersionFlag`).
     ///
     /// **NOTE:** Any leading `-` characters will be stripped
     ///
@@ -316,6 +686,61 @@ impl<'n, 'l, 'h, 'g, 'p, 'r> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
         self
     }
 
+    /// Adds a hidden alternate long version of the argument without the preceding `--`. The
+    /// argument may be used at runtime with either its long version or any of its aliases, but
+    /// aliases added this way are not displayed in the usage or help information. This is useful
+    /// to keep accepting an argument after it has been renamed.
+    ///
+    /// **NOTE:** Any leading `-` characters will be stripped
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("output").takes_value(true)
+    /// .long("output")
+    /// .alias("out")
+    /// # ).get_matches();
+    pub fn alias(mut self, l: &'l str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.add_alias(l, false);
+        self
+    }
+
+    /// Adds an alternate long version of the argument without the preceding `--`, which unlike
+    /// `alias()` is displayed in the usage string (i.e. `--output|--out <output>`) and in the
+    /// help information. This is useful for important arguments which have been renamed, so users
+    /// of the old name can find the new one.
+    ///
+    /// **NOTE:** Any leading `-` characters will be stripped
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("output").takes_value(true)
+    /// .long("output")
+    /// .visible_alias("out")
+    /// # ).get_matches();
+    pub fn visible_alias(mut self, l: &'l str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.add_alias(l, true);
+        self
+    }
+
+    fn add_alias(&mut self, l: &'l str, visible: bool) {
+        let l = l.trim_left_matches(|c| c == '-');
+        if let Some(ref mut vec) = self.aliases {
+            vec.push((l, visible));
+        } else {
+            self.aliases = Some(vec![(l, visible)]);
+        }
+    }
+
     /// Sets the help text of the argument that will be displayed to the user
     /// when they print the usage/help information. 
     ///
@@ -334,6 +759,29 @@ impl<'n, 'l, 'h, 'g, 'p, 'r> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
         self
     }
 
+    /// Sets a detailed help text of the argument, which may span multiple lines or paragraphs.
+    /// It is displayed instead of the text set with `help()` when the user requests `--help`,
+    /// while `-h` continues to display the brief help text. If no long help is set, `--help`
+    /// falls back to the brief help text.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("conifg")
+    /// .help("The config file used by the myprog")
+    /// .long_help("The config file used by the myprog.
+    ///
+    /// If omitted, myprog looks for myprog.toml in the current directory.")
+    /// # ).get_matches();
+    pub fn long_help(mut self, h: &'h str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.long_help = Some(h);
+        self
+    }
+
     /// Sets whether or not the argument is required by default. Required by
     /// default means it is required, when no other mutually exlusive rules have
     /// been evaluated. Mutually exclusive rules take precedence over being required
@@ -358,6 +806,122 @@ impl<'n, 'l, 'h, 'g, 'p, 'r> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
         self
     }
 
+    /// Sets this argument as required unless the given argument (or an argument of the given
+    /// group) was used, i.e. an input file which is required unless `--stdin` was supplied.
+    ///
+    /// **NOTE:** Flags cannot be required
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("input").index(1)
+    /// .required_unless("stdin")
+    /// # ).get_matches();
+    pub fn required_unless(self, name: &'r str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.required_unless_any(vec![name])
+    }
+
+    /// Sets this argument as required unless *any* of the given arguments (or arguments of the
+    /// given groups) were used.
+    ///
+    /// **NOTE:** Flags cannot be required
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("input").index(1)
+    /// .required_unless_any(vec!["stdin", "url"])
+    /// # ).get_matches();
+    pub fn required_unless_any(mut self, names: Vec<&'r str>) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        if let Some(ref mut vec) = self.r_unless {
+            for n in names {
+                vec.push(n);
+            }
+        } else {
+            self.r_unless = Some(names);
+        }
+        self
+    }
+
+    /// Sets this argument as required unless *all* of the given arguments (or arguments of the
+    /// given groups) were used.
+    ///
+    /// **NOTE:** Flags cannot be required
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("config").long("config").takes_value(true)
+    /// .required_unless_all(vec!["host", "port"])
+    /// # ).get_matches();
+    pub fn required_unless_all(mut self, names: Vec<&'r str>) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.r_unless_all = true;
+        self.required_unless_any(names)
+    }
+
+    /// Sets this argument as required if another argument was used with a specific value, i.e.
+    /// an output file which is required when `--mode file` was supplied. This may be used
+    /// multiple times, in which case the argument is required if *any* of the arguments was used
+    /// with its value.
+    ///
+    /// **NOTE:** Flags cannot be required
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("output").long("output").takes_value(true)
+    /// .required_if_eq("mode", "file")
+    /// # ).get_matches();
+    pub fn required_if_eq(mut self, name: &'r str, val: &'p str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        if let Some(ref mut vec) = self.r_ifs {
+            vec.push((name, val));
+        } else {
+            self.r_ifs = Some(vec![(name, val)]);
+        }
+        self
+    }
+
+    /// Sets an argument by name that is required when this argument is used with a specific
+    /// value, i.e. `--format csv` requiring `--delimiter`. This may be used multiple times, for
+    /// different values or arguments.
+    ///
+    /// **NOTE:** This setting only applies to options and positional arguments
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("format").long("format").takes_value(true)
+    /// .requires_if("csv", "delimiter")
+    /// # ).get_matches();
+    pub fn requires_if(mut self, val: &'p str, name: &'r str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        if let Some(ref mut vec) = self.requires_ifs {
+            vec.push((val, name));
+        } else {
+            self.requires_ifs = Some(vec![(val, name)]);
+        }
+        self
+    }
+
     /// Sets a mutually exclusive argument by name. I.e. when using this argument, 
     /// the following argument can't be present.
     ///
@@ -464,9 +1028,87 @@ impl<'n, 'l, 'h, 'g, 'p, 'r> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
         self
     }
 
+    /// Sets the only arguments (or groups) by names which may be used with this argument, i.e.
+    /// when using this argument, any other argument can't be present. This is the inverse of
+    /// `conflicts_with_all()`, for arguments such as `--version-json` which only combine with one
+    /// or two others, so the arguments defined later don't need to be kept in sync.
+    ///
+    /// **NOTE:** The automatic `help` and `version` flags are always allowed
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let myprog = App::new("myprog").arg(Arg::with_name("version-json")
+    /// .conflicts_with_everything_except(
+    ///        vec!["pretty"])
+    /// # ).get_matches();
+    pub fn conflicts_with_everything_except(mut self, names: Vec<&'r str>) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        if let Some(ref mut vec) = self.conflicts_except {
+            for n in names {
+                vec.push(n);
+            }
+        } else {
+            self.conflicts_except = Some(names);
+        }
+        self
+    }
+
+    /// Sets an argument by name which this argument overrides, i.e. `--quiet` overriding an
+    /// earlier `--verbose`. Unlike conflicting arguments, using both isn't an error, instead
+    /// whichever is used *last* wins and the other one is removed from the matches as if it
+    /// hadn't been used at all.
+    ///
+    /// **NOTE:** Overrides only need to be set for one of the two arguments, they do not need to
+    /// be set for each.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let myprog = App::new("myprog").arg(Arg::with_name("quiet")
+    /// .overrides_with("verbose")
+    /// # ).get_matches();
+    pub fn overrides_with(mut self, name: &'r str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        if let Some(ref mut vec) = self.overrides {
+            vec.push(name);
+        } else {
+            self.overrides = Some(vec![name]);
+        }
+        self
+    }
+
+    /// Sets arguments by names which this argument overrides, whichever is used *last* wins.
+    /// See `overrides_with()`.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let myprog = App::new("myprog").arg(Arg::with_name("quiet")
+    /// .overrides_with_all(
+    ///        vec!["verbose", "debug"])
+    /// # ).get_matches();
+    pub fn overrides_with_all(mut self, names: Vec<&'r str>) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        if let Some(ref mut vec) = self.overrides {
+            for n in names {
+                vec.push(n);
+            }
+        } else {
+            self.overrides = Some(names);
+        }
+        self
+    }
+
     /// Sets an argument by name that is required when this one is presnet I.e. when
     /// using this argument, the following argument *must* be present.
     ///
+    /// The name may also be that of an `ArgGroup`, in which case at least one member of the
+    /// group must be present.
+    ///
     /// **NOTE:** Mutually exclusive rules take precedence over being required
     ///
     ///
@@ -539,6 +1181,10 @@ impl<'n, 'l, 'h, 'g, 'p, 'r> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
     /// Also, the name will be used when printing the help/usage information 
     /// to the user. 
     ///
+    /// **NOTE:** Indexes don't need to be consecutive, gaps (i.e. an index of 1 and 3 but no 2)
+    /// are treated as absent, so the positional arguments are simply used in the order of their
+    /// indexes.
+    ///
     ///
     /// # Example
     ///
@@ -577,10 +1223,9 @@ impl<'n, 'l, 'h, 'g, 'p, 'r> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
         self
     }
 
-    /// Specifies a list of possible values for this argument. At runtime, clap verifies that only
-    /// one of the specified values was used, or fails with a usage string.
-    /// 
-    /// **NOTE:** This setting only applies to options and positional arguments 
+    /// Specifies if the flag or option may be used more than once, i.e. `-d -d -d` or
+    /// `-o a -o b`, where an option still takes a single value each time it's used (unless
+    /// `multiple_values` is set). For flags and options this is the same as `multiple`.
     ///
     ///
     /// # Example
@@ -589,21 +1234,22 @@ impl<'n, 'l, 'h, 'g, 'p, 'r> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
     /// # use clap::{App, Arg};
     /// # let matches = App::new("myprog")
     /// #                 .arg(
-    /// # Arg::with_name("debug").index(1)
-    /// .possible_values(vec!["fast", "slow"])
+    /// # Arg::with_name("file").short("f").takes_value(true)
+    /// .multiple_occurrences(true)
     /// # ).get_matches();
-    pub fn possible_values(mut self, names: Vec<&'p str>) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
-        if let Some(ref mut vec) = self.possible_vals {
-            for n in names {
-                vec.push(n);
-            }
-        } else {
-            self.possible_vals = Some(names);
-        }
+    pub fn multiple_occurrences(mut self, multi: bool) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.multiple = multi;
         self
     }
 
-    /// Specifies the name of the group the argument belongs to.
+    /// Specifies if the option consumes all of the following values each time it's used, i.e.
+    /// `-f a b c`, until the next argument starting with a `-` (such as another flag, option, or
+    /// `--`). This doesn't allow the option itself to be used more than once (see
+    /// `multiple_occurrences`), and is best avoided for options which may precede positional
+    /// arguments, as those would be consumed as values of the option.
+    ///
+    /// **NOTE:** This setting only applies to options (positional arguments accept multiple
+    /// values with `multiple`)
     ///
     ///
     /// # Example
@@ -612,11 +1258,671 @@ impl<'n, 'l, 'h, 'g, 'p, 'r> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
     /// # use clap::{App, Arg};
     /// # let matches = App::new("myprog")
     /// #                 .arg(
-    /// # Arg::with_name("debug").index(1)
-    /// .group("mode")
+    /// # Arg::with_name("file").short("f").takes_value(true)
+    /// .multiple_values(true)
     /// # ).get_matches();
-    pub fn group(mut self, name: &'g str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
-        self.group = Some(name);
+    pub fn multiple_values(mut self, multi: bool) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.multiple_vals = multi;
+        self
+    }
+
+    /// Specifies if values starting with a `-` are accepted, i.e. `-5` or `--foo`, where they'd
+    /// otherwise be taken for an (unknown) flag or option. This applies to positional arguments
+    /// and to the values an option with `multiple_values` consumes after its required ones.
+    /// Arguments which are valid flags or options of the application, as well as `--`, are
+    /// still parsed as usual.
+    ///
+    /// **NOTE:** An option always consumes the argument following it as its required value,
+    /// whether or not it starts with a `-` (i.e. `--offset -5`)
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("number").index(1)
+    /// .allow_hyphen_values(true)
+    /// # ).get_matches();
+    pub fn allow_hyphen_values(mut self, a: bool) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.allow_hyphen_vals = a;
         self
     }
+
+    /// Takes `--` as a literal value of the option while it's consuming values (i.e. to forward
+    /// it to another program, as in `--args -- -v`), instead of ending its values. By default
+    /// `--` ends the values of an option (and starts the positional arguments), and it's an error
+    /// if the option still requires more values at that point.
+    ///
+    /// **NOTE:** `--opt=--` always passes `--` as the value
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("args").long("args").takes_value(true).multiple_values(true)
+    /// .separator_as_value(true)
+    /// # ).get_matches();
+    pub fn separator_as_value(mut self, s: bool) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.sep_as_val = s;
+        self
+    }
+
+    /// Specifies that a positional argument is only populated from the arguments following `--`,
+    /// which cleanly separates the arguments of the program from those it passes through (i.e.
+    /// to another program). Supplying a value for it before `--` is an error, and it's displayed
+    /// in the usage as `[-- <name>...]`.
+    ///
+    /// **NOTE:** This must be the positional argument with the highest index. The arguments
+    /// following `--` are all taken as its values, even if previous positional arguments weren't
+    /// supplied
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("args").multiple(true)
+    /// .last(true)
+    /// # ).get_matches();
+    pub fn last(mut self, l: bool) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.last = l;
+        self
+    }
+
+    /// Specifies that this argument is also available to all subcommands (recursively), i.e.
+    /// `myprog test --verbose` as well as `myprog --verbose test`. The argument is only present
+    /// in the matches of the (sub)command where it was used.
+    ///
+    /// If a subcommand has its own argument with the same name, the subcommand's argument is used
+    /// instead of the global one. If a subcommand has a *different* argument which uses the same
+    /// short or long, `clap` will panic when parsing begins.
+    ///
+    /// **NOTE:** This setting only applies to flags and options
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("verbose").long("verbose")
+    /// .global(true)
+    /// # ).get_matches();
+    pub fn global(mut self, g: bool) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.global = g;
+        self
+    }
+
+    /// Specifies a list of possible values for this argument. At runtime, clap verifies that only
+    /// one of the specified values was used, or fails with a usage string.
+    /// 
+    /// **NOTE:** This setting only applies to options and positional arguments 
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("debug").index(1)
+    /// .possible_values(vec!["fast", "slow"])
+    /// # ).get_matches();
+    pub fn possible_values(mut self, names: Vec<&'p str>) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        if let Some(ref mut vec) = self.possible_vals {
+            for n in names {
+                vec.push(n);
+            }
+        } else {
+            self.possible_vals = Some(names);
+        }
+        self
+    }
+
+    /// Specifies a value to use when this argument isn't supplied at runtime. The value is
+    /// returned by `value_of()` and `values_of()` as if the user had supplied it, and the
+    /// argument satisfies being `required`. Use `ArgMatches::is_default()` to check whether the
+    /// user actually supplied a value.
+    ///
+    /// **NOTE:** This setting only applies to options and positional arguments
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("config").short("c").takes_value(true)
+    /// .default_value("config.json")
+    /// # ).get_matches();
+    pub fn default_value(self, val: &'p str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.default_value_os(OsStr::new(val))
+    }
+
+    /// Specifies a value to use when this argument isn't supplied at runtime, the same as
+    /// `default_value()` but for a value which isn't valid UTF-8, such as a path. The value is
+    /// returned by `value_of_os()` and `values_of_os()` unchanged.
+    ///
+    /// **NOTE:** This setting only applies to options and positional arguments
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # use std::ffi::OsStr;
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("config").short("c").takes_value(true)
+    /// .default_value_os(OsStr::new("config.json"))
+    /// # ).get_matches();
+    pub fn default_value_os(mut self, val: &'p OsStr) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.default_val = Some(val);
+        self
+    }
+
+    /// Specifies a value to use when this argument isn't supplied at runtime, but another
+    /// argument was (with the given value, if any). This may be used multiple times, in which
+    /// case the first matching condition applies. Conditional defaults are evaluated after
+    /// parsing, before the required arguments are validated, and take precedence over
+    /// `default_value`.
+    ///
+    /// **NOTE:** The conditions only consider the arguments supplied by the user (or from the
+    /// environment), not the default values of other arguments
+    ///
+    /// **NOTE:** This setting only applies to options and positional arguments
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("port").long("port").takes_value(true)
+    /// .default_value_if("tls", None, "443")
+    /// .default_value_if("proto", Some("http"), "80")
+    /// # ).get_matches();
+    pub fn default_value_if(mut self, name: &'r str, val: Option<&'p str>, default: &'p str)
+                            -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        if let Some(ref mut vec) = self.default_val_ifs {
+            vec.push((name, val, default));
+        } else {
+            self.default_val_ifs = Some(vec![(name, val, default)]);
+        }
+        self
+    }
+
+    /// Specifies the value used when an option whose value is optional (see `min_values(0)`) is
+    /// used without one, i.e. `--color` meaning `--color=auto` while `--color=never` still works.
+    ///
+    /// **NOTE:** This setting only applies to options
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("color").long("color").takes_value(true)
+    /// .min_values(0)
+    /// .default_missing_value("auto")
+    /// # ).get_matches();
+    pub fn default_missing_value(mut self, val: &'p str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.default_missing_val = Some(val);
+        self
+    }
+
+    /// Specifies an environment variable the value of this option is read from, if the option
+    /// isn't used on the command line (taking precedence over
+    /// `default_value` and `default_value_if`). The value is
+    /// validated just like one supplied on the command line, and is split at the value delimiter
+    /// if there is one. Unset and empty environment variables are ignored.
+    ///
+    /// If the argument has neither a `short` nor a `long`, it can *only* be supplied through the
+    /// environment variable, and isn't displayed in the usage and help information.
+    ///
+    /// **NOTE:** This setting only applies to options
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("token")
+    /// .env("MYPROG_TOKEN")
+    /// # ).get_matches();
+    pub fn env(mut self, var: &'p str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.env = Some(var);
+        self
+    }
+
+    /// Specifies the character which separates multiple values within a single value of an
+    /// option, i.e. `--features foo,bar,baz` yields the three values `foo`, `bar`, and `baz`.
+    /// Setting a delimiter also enables splitting values at it (see `use_delimiter()`).
+    ///
+    /// By default options which accept `multiple` values are split at `,`.
+    ///
+    /// **NOTE:** This setting only applies to options
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("features").long("features").takes_value(true)
+    /// .value_delimiter(':')
+    /// # ).get_matches();
+    pub fn value_delimiter(mut self, d: char) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.val_delim = Some(d);
+        self.use_delim = Some(true);
+        self
+    }
+
+    /// Specifies whether values of this option are split at the value delimiter (`,` unless set
+    /// with `value_delimiter()`). By default this is only the case for options which accept
+    /// `multiple` values, so `use_delimiter(false)` allows values containing a `,` to be used
+    /// with those as-is.
+    ///
+    /// **NOTE:** This setting only applies to options
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("name").long("name").takes_value(true).multiple(true)
+    /// .use_delimiter(false)
+    /// # ).get_matches();
+    pub fn use_delimiter(mut self, d: bool) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.use_delim = Some(d);
+        self
+    }
+
+    /// Specifies that multiple values of this option must be supplied separated by the value
+    /// delimiter in a single occurrence (i.e. `--features foo,bar`), instead of by repeating the
+    /// option (i.e. `--features foo --features bar`), which is reported as an error. This also
+    /// enables splitting values at the delimiter.
+    ///
+    /// **NOTE:** This setting only applies to options
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("features").long("features").takes_value(true)
+    /// .require_delimiter(true)
+    /// # ).get_matches();
+    pub fn require_delimiter(mut self, d: bool) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.req_delim = d;
+        self
+    }
+
+    /// Specifies the exact number of values this option requires each time it's used, i.e.
+    /// `--point 3 7` for `number_of_values(2)`. If fewer values are supplied an error is
+    /// displayed. Values following the option are consumed even if they start with a `-`.
+    ///
+    /// **NOTE:** This setting only applies to options
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("point").long("point").takes_value(true)
+    /// .number_of_values(2)
+    /// # ).get_matches();
+    pub fn number_of_values(mut self, qty: u8) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        if qty == 0 {
+            panic!("Argument \"{}\" requires number_of_values() to be at least 1", self.name);
+        }
+        self.num_vals = Some(qty);
+        self
+    }
+
+    /// Specifies the name of the value displayed in the usage and help information instead of
+    /// the argument's name, i.e. `--config <FILE>` instead of `--config <config>`. For positional
+    /// arguments this is the name they're displayed as, i.e. `<FILE>` for an argument named
+    /// `input`, while the argument is still retrieved by its name.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("config").long("config").takes_value(true)
+    /// .value_name("FILE")
+    /// # ).get_matches();
+    pub fn value_name(self, name: &'n str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.value_names(vec![name])
+    }
+
+    /// Specifies the names of each of the values displayed in the usage and help information,
+    /// i.e. `--copy <SRC> <DST>`. Unless `number_of_values` is also set, the option requires as
+    /// many values as names are given.
+    ///
+    /// **NOTE:** Positional arguments only use the first name (see `value_name`)
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("copy").long("copy").takes_value(true)
+    /// .value_names(vec!["SRC", "DST"])
+    /// # ).get_matches();
+    pub fn value_names(mut self, names: Vec<&'n str>) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        if names.is_empty() {
+            panic!("Argument \"{}\" requires value_names() to contain at least one name", self.name);
+        }
+        self.val_names = Some(names);
+        self
+    }
+
+    /// Specifies the kind of value the argument accepts, i.e. a path to a file, so shell
+    /// completion scripts can offer suitable candidates. See `ValueHint` for the kinds of values.
+    ///
+    /// **NOTE:** This setting only applies to options and positional arguments
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, ValueHint};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("input").index(1)
+    /// .value_hint(ValueHint::FilePath)
+    /// # ).get_matches();
+    pub fn value_hint(mut self, hint: ValueHint) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.value_hint = Some(hint);
+        self
+    }
+
+    /// Sets a function which lists the candidate values of the argument at runtime, i.e. the
+    /// profiles which currently exist on disk, so completions can offer them. Unlike
+    /// `possible_values()` the candidates are only suggestions, so other values are still
+    /// accepted.
+    ///
+    /// **NOTE:** This setting only applies to options and positional arguments
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// fn profiles() -> Vec<String> {
+    ///     vec!["default".to_owned(), "release".to_owned()]
+    /// }
+    ///
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("profile").long("profile").takes_value(true)
+    /// .value_candidates(profiles)
+    /// # ).get_matches();
+    pub fn value_candidates(mut self, f: fn() -> Vec<String>) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.candidates = Some(f);
+        self
+    }
+
+    /// Specifies the minimum number of values this argument requires in total (across all of
+    /// its occurrences), i.e. `--input a.txt --input b.txt` for `min_values(2)`. If fewer values
+    /// are supplied an error is displayed. The requirement only applies when the argument is
+    /// used, see `required()` to require the argument itself.
+    ///
+    /// **NOTE:** This setting only applies to options and positional arguments which accept
+    /// `multiple` values, or options whose value is optional, with `min_values(0)`. Such an
+    /// option never consumes the following argument as its value when it's used bare, the value
+    /// must be attached instead (i.e. `--color=never`). See `default_missing_value()`.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("input").index(1).multiple(true)
+    /// .min_values(2)
+    /// # ).get_matches();
+    pub fn min_values(mut self, qty: u8) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.min_vals = Some(qty);
+        self
+    }
+
+    /// Specifies the maximum number of values this argument accepts in total (across all of its
+    /// occurrences). If more values are supplied an error is displayed.
+    ///
+    /// **NOTE:** This setting only applies to options and positional arguments which accept
+    /// `multiple` values
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("input").index(1).multiple(true)
+    /// .max_values(3)
+    /// # ).get_matches();
+    pub fn max_values(mut self, qty: u8) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.max_vals = Some(qty);
+        self
+    }
+
+    /// Specifies the name of the group the argument belongs to.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("debug").index(1)
+    /// .group("mode")
+    /// # ).get_matches();
+    pub fn group(mut self, name: &'g str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.group = Some(name);
+        self
+    }
+
+    /// Sets a custom message to display to the user in place of the generic error message when
+    /// this argument is required but wasn't supplied, or was supplied with an invalid value (i.e.
+    /// one not in the `possible_values`). This allows giving domain specific guidance, such as
+    /// which command to run first.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("token").long("token").takes_value(true).required(true)
+    /// .error_message("No token found, run `myprog login` first")
+    /// # ).get_matches();
+    pub fn error_message(mut self, msg: &'h str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.error_message = Some(msg);
+        self
+    }
+
+    /// Sets the spelling of this argument used in error messages, independent of its name and of
+    /// how it was supplied, i.e. always the long form of an option even if its short was used.
+    /// By default errors refer to the argument as it was supplied, or as it's displayed in the
+    /// usage.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("config").short("c").long("config").takes_value(true)
+    /// .display_name("--config")
+    /// # ).get_matches();
+    pub fn display_name(mut self, name: &'h str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.disp_name = Some(name);
+        self
+    }
+
+    /// Marks the argument as deprecated, with a note such as what to use instead (or an empty
+    /// string for no note). The argument continues to work as usual, but it is annotated with
+    /// `[deprecated: <note>]` in the help information and man page, and a warning is displayed
+    /// on stderr whenever it's used at runtime.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("out").long("out").takes_value(true)
+    /// .deprecated("since 2.0: use --output")
+    /// # ).get_matches();
+    pub fn deprecated(mut self, note: &'h str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.deprecated = Some(note);
+        self
+    }
+
+    /// Omits the argument from the help information, while it can still be used at runtime as
+    /// usual. This is useful for deprecated or internal arguments.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("debug-internals").long("debug-internals")
+    /// .hidden(true)
+    /// # ).get_matches();
+    pub fn hidden(mut self, h: bool) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.hidden = h;
+        self
+    }
+
+    /// Lists the argument in the help under a section with the given heading, instead of the
+    /// default FLAGS, OPTIONS, or POSITIONAL ARGUMENTS section. All arguments with the same heading
+    /// (and those of an `ArgGroup` with that heading) are listed together, in sections sorted by
+    /// their heading. Sections with a custom heading follow the default ones.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("proxy").long("proxy").takes_value(true)
+    /// .help_heading("NETWORK OPTIONS")
+    /// # ).get_matches();
+    pub fn help_heading(mut self, h: &'h str) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.help_heading = Some(h);
+        self
+    }
+
+    /// Enables a behavioral setting of the argument (see `ArgSettings` for the available
+    /// settings), which is equivalent to the builder method of the setting, i.e.
+    /// `ArgSettings::Required` and `required(true)`.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, ArgSettings};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::with_name("config").long("config")
+    /// .setting(ArgSettings::TakesValue)
+    /// # ).get_matches();
+    pub fn setting(mut self, s: ArgSettings) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.set(s, true);
+        self
+    }
+
+    /// Disables a behavioral setting of the argument (see `setting()`)
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, ArgSettings};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg(
+    /// # Arg::from_usage("-c, --config=<FILE> 'Sets a config file'")
+    /// .unset_setting(ArgSettings::Required)
+    /// # ).get_matches();
+    pub fn unset_setting(mut self, s: ArgSettings) -> Arg<'n, 'l, 'h, 'g, 'p, 'r> {
+        self.set(s, false);
+        self
+    }
+
+    /// Checks whether a behavioral setting of the argument is enabled (see `setting()`)
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{Arg, ArgSettings};
+    /// let arg = Arg::from_usage("-c, --config=<FILE> 'Sets a config file'");
+    /// assert!(arg.is_set(ArgSettings::TakesValue));
+    /// ```
+    pub fn is_set(&self, s: ArgSettings) -> bool {
+        match s {
+            ArgSettings::Required          => self.required,
+            ArgSettings::TakesValue        => self.takes_value,
+            ArgSettings::Multiple          => self.multiple,
+            ArgSettings::MultipleValues    => self.multiple_vals,
+            ArgSettings::AllowHyphenValues => self.allow_hyphen_vals,
+            ArgSettings::Last              => self.last,
+            ArgSettings::Global            => self.global,
+            ArgSettings::RequireDelimiter  => self.req_delim,
+            ArgSettings::Hidden            => self.hidden
+        }
+    }
+
+    // Turns a setting on or off, by way of the field backing it
+    fn set(&mut self, s: ArgSettings, on: bool) {
+        match s {
+            ArgSettings::Required          => self.required = on,
+            ArgSettings::TakesValue        => self.takes_value = on,
+            ArgSettings::Multiple          => self.multiple = on,
+            ArgSettings::MultipleValues    => self.multiple_vals = on,
+            ArgSettings::AllowHyphenValues => self.allow_hyphen_vals = on,
+            ArgSettings::Last              => self.last = on,
+            ArgSettings::Global            => self.global = on,
+            ArgSettings::RequireDelimiter  => self.req_delim = on,
+            ArgSettings::Hidden            => self.hidden = on
+        }
+    }
 }
@@ -6,10 +6,21 @@ pub struct FlagBuilder<'n> {
     /// The long version of the flag (i.e. word)
     /// without the preceding `--`
     pub long: Option<&'n str>,
+    /// Alternate long versions of the flag
+    /// and whether each is visible in the
+    /// usage and help information
+    pub aliases: Option<Vec<(&'n str, bool)>>,
+    /// Ignores the aliases, and doesn't
+    /// display them, see `App::strict()`
+    pub strict: bool,
     /// The string of text that will displayed to 
     /// the user when the application's `help` 
     /// text is displayed
     pub help: Option<&'n str>,
+    /// The detailed help text displayed
+    /// instead of `help` when the user
+    /// requests `--help`
+    pub long_help: Option<&'n str>,
     /// Determines if multiple instances of the same
     /// flag are allowed
     /// I.e. `-v -v -v` or `-vvv`
@@ -17,17 +28,45 @@ pub struct FlagBuilder<'n> {
     /// A list of names for other arguments that
     /// *may not* be used with this flag
     pub blacklist: Option<HashSet<&'n str>>,
+    /// A list of names for other arguments
+    /// which this flag overrides, whichever
+    /// is used last wins
+    pub overrides: Option<Vec<&'n str>>,
     /// A list of names of other arguments that 
     /// are *required* to be used when this
     /// flag is used
     pub requires: Option<HashSet<&'n str>>,
+    /// The groups this flag belongs to,
+    /// whether set with `Arg::group()` or
+    /// `ArgGroup::args()`
+    pub groups: Vec<&'n str>,
     /// The short version (i.e. single character)
     /// of the argument, no preceding `-`
     pub short: Option<char>,
+    /// A custom message displayed to
+    /// the user when this flag is
+    /// required but missing
+    pub error_message: Option<&'n str>,
+    /// The spelling of the flag
+    /// used in error messages
+    pub disp_name: Option<&'n str>,
+    /// A note on what to use instead,
+    /// if the flag is deprecated
+    pub deprecated: Option<&'n str>,
+    /// Whether the argument is omitted from the help information
+    pub hidden: bool,
+    /// The heading of the help section the argument is listed in, if not the default one
+    pub help_heading: Option<&'n str>,
 }
 
 impl<'n> Display for FlagBuilder<'n> {
     fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "{}", if self.long.is_some() { format!("--{}", self.long.unwrap())} else {format!("-{}", self.short.unwrap())})
+        try!(write!(f, "{}", if self.long.is_some() { format!("--{}", self.long.unwrap())} else {format!("-{}", self.short.unwrap())}));
+        if let (false, Some(ref aliases)) = (self.strict, self.aliases.as_ref()) {
+            for &(a, _) in aliases.iter().filter(|&&(_, visible)| visible) {
+                try!(write!(f, "|--{}", a));
+            }
+        }
+        Ok(())
     }
 }
\ No newline at end of file
@@ -1,6 +1,10 @@
 use std::collections::HashSet;
 use std::collections::BTreeSet;
 use std::fmt::{ Display, Formatter, Result };
+use std::ffi::{OsStr, OsString};
+
+use osstr;
+use args::ValueHint;
 
 pub struct OptBuilder<'n> {
     pub name: &'n str,
@@ -8,13 +12,31 @@ pub struct OptBuilder<'n> {
     pub short: Option<char>,
     /// The long version of the flag (i.e. word) without the preceding `--`
     pub long: Option<&'n str>,
+    /// The environment variable the value is read from, if the option isn't used
+    pub env: Option<&'n str>,
+    /// Alternate long versions of the option and whether each is visible in the usage and
+    /// help information
+    pub aliases: Option<Vec<(&'n str, bool)>>,
+    /// Ignores the aliases, and doesn't display them, see `App::strict()`
+    pub strict: bool,
     /// The string of text that will displayed to the user when the application's
     /// `help` text is displayed
     pub help: Option<&'n str>,
+    /// The detailed help text displayed instead of `help` when the user requests `--help`
+    pub long_help: Option<&'n str>,
     /// Allow multiple occurrences of an option argument such as "-c some -c other"
     pub multiple: bool,
+    /// Consume all following values instead of one, such as "-c some other"
+    pub multiple_vals: bool,
+    /// Accept values starting with a `-` once the required values were consumed
+    pub allow_hyphen_vals: bool,
+    /// Take `--` as a value instead of ending the values
+    pub sep_as_val: bool,
     /// A list of names for other arguments that *may not* be used with this flag
     pub blacklist: Option<HashSet<&'n str>>,
+    /// A list of names for other arguments which this argument overrides, whichever is used last
+    /// wins
+    pub overrides: Option<Vec<&'n str>>,
     /// If this is a required by default when using the command line program
     /// i.e. a configuration file that's required for the program to function
     /// **NOTE:** required by default means, it is required *until* mutually
@@ -22,13 +44,121 @@ pub struct OptBuilder<'n> {
     pub required: bool,
     /// A list of possible values for this argument
     pub possible_vals: Option<BTreeSet<&'n str>>,
+    /// The value used when this argument isn't supplied at runtime
+    pub default_val: Option<&'n OsStr>,
+    /// A list of other arguments, optional values, and the value used when this argument isn't
+    /// supplied but the other argument was
+    pub default_val_ifs: Option<Vec<(&'n str, Option<&'n str>, &'n str)>>,
+    /// The value used when the option's value is optional and it's used without one
+    pub default_missing_val: Option<&'n str>,
+    /// The character at which values are split, if they are
+    pub val_delim: Option<char>,
+    /// Whether multiple values must be supplied delimited instead of by repeating the option
+    pub req_delim: bool,
+    /// The exact number of values required each time the option is used
+    pub num_vals: Option<u8>,
+    /// The names of the values displayed in the usage and help information
+    pub val_names: Option<Vec<&'n str>>,
+    /// The kind of value accepted, used by shell completions
+    pub value_hint: Option<ValueHint>,
+    /// The function listing the candidate values at runtime, used by shell completions
+    pub candidates: Option<fn() -> Vec<String>>,
+    /// The minimum number of values required in total
+    pub min_vals: Option<u8>,
+    /// The maximum number of values accepted in total
+    pub max_vals: Option<u8>,
     /// A list of names of other arguments that are *required* to be used when 
     /// this flag is used
     pub requires: Option<HashSet<&'n str>>,
+    /// The groups this argument belongs to, whether set with `Arg::group()` or
+    /// `ArgGroup::args()`
+    pub groups: Vec<&'n str>,
+    /// A list of names of other arguments which, when used, make this argument no longer required
+    pub r_unless: Option<Vec<&'n str>>,
+    /// Whether all of the arguments in `r_unless` must be used, instead of any of them
+    pub r_unless_all: bool,
+    /// A list of other arguments and values which make this argument required
+    pub r_ifs: Option<Vec<(&'n str, &'n str)>>,
+    /// A list of values and other arguments which are required when this argument has the value
+    pub requires_ifs: Option<Vec<(&'n str, &'n str)>>,
+    /// A custom message displayed when this argument is missing or has an invalid value
+    pub error_message: Option<&'n str>,
+    /// The spelling of the argument used in error messages
+    pub disp_name: Option<&'n str>,
+    /// A note on what to use instead, if the argument is deprecated
+    pub deprecated: Option<&'n str>,
+    /// Whether the argument is omitted from the help information
+    pub hidden: bool,
+    /// The heading of the help section the argument is listed in, if not the default one
+    pub help_heading: Option<&'n str>,
+}
+
+impl<'n> OptBuilder<'n> {
+    /// Whether the option can't be used on the command line at all, and is only populated from
+    /// its environment variable
+    pub fn env_only(&self) -> bool {
+        self.short.is_none() && self.long.is_none()
+    }
+
+    /// The option as referred to in error messages, i.e. `--config`, `-c`, or `$MYPROG_CONFIG`
+    pub fn switch(&self) -> String {
+        match (self.long, self.short, self.env) {
+            (Some(l), _, _)    => format!("--{}", l),
+            (None, Some(s), _) => format!("-{}", s),
+            (None, None, e)    => format!("${}", e.unwrap_or(self.name))
+        }
+    }
+
+    /// The placeholders of the values displayed in the usage and help information, i.e.
+    /// `<point> <point>...`, using the value names if there are any (the last one is repeated if
+    /// more values are required than there are names). Optional values are enclosed in brackets.
+    /// A single placeholder of an option which accepts multiple values separated by a delimiter
+    /// shows the delimiter, i.e. `<point,...>`.
+    pub fn vals_usage(&self) -> String {
+        let names = self.val_names.as_ref().map(|n| &n[..]).unwrap_or(&[]);
+        let num_vals = self.num_vals.map(|n| n as usize).unwrap_or(if names.is_empty() { 1 } else { names.len() });
+        let delimited = match self.val_delim {
+            // Multiple occurrences split their values by default, which the trailing "..." covers
+            Some(d) if num_vals == 1 && (!self.multiple || self.multiple_vals || self.req_delim) => Some(d),
+            _                                                                                     => None
+        };
+        let mut s = (0..num_vals).map(|i| {
+                                     let name = names.get(i).or(names.last()).unwrap_or(&self.name);
+                                     match delimited {
+                                         Some(d) => format!("<{}{}...>", name, d),
+                                         None    => format!("<{}>", name)
+                                     }
+                                 })
+                                 .collect::<Vec<_>>()
+                                 .join(" ");
+        // The delimited placeholder already shows that multiple values are accepted
+        if self.multiple || (self.multiple_vals && delimited.is_none()) { s.push_str("..."); }
+        if self.optional_val() { format!("[{}]", s) } else { s }
+    }
+
+    /// Whether the option may be used without a value, in which case it never consumes the
+    /// following argument as its value
+    pub fn optional_val(&self) -> bool {
+        self.min_vals == Some(0)
+    }
+
+    /// Splits a single value supplied at runtime into the values it delimits
+    pub fn split_vals(&self, val: &OsStr) -> Vec<OsString> {
+        match self.val_delim {
+            Some(d) => osstr::split(val, d),
+            None    => vec![val.to_os_string()]
+        }
+    }
 }
 
 impl<'n> Display for OptBuilder<'n> {
     fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "{} <{}>{}", if self.long.is_some() { format!("--{}", self.long.unwrap())} else {format!("-{}", self.short.unwrap())}, self.name, if self.multiple{"..."}else{""})
+        try!(write!(f, "{}", self.switch()));
+        if let (false, Some(ref aliases)) = (self.strict, self.aliases.as_ref()) {
+            for &(a, _) in aliases.iter().filter(|&&(_, visible)| visible) {
+                try!(write!(f, "|--{}", a));
+            }
+        }
+        write!(f, " {}", self.vals_usage())
     }
 }
\ No newline at end of file
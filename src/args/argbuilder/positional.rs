@@ -1,12 +1,17 @@
 use std::collections::HashSet;
 use std::collections::BTreeSet;
 use std::fmt::{ Display, Formatter, Result };
+use std::ffi::OsStr;
+
+use args::ValueHint;
 
 pub struct PosBuilder<'n> {
     pub name: &'n str,
     /// The string of text that will displayed to the user when the application's
     /// `help` text is displayed
     pub help: Option<&'n str>,
+    /// The detailed help text displayed instead of `help` when the user requests `--help`
+    pub long_help: Option<&'n str>,
     /// If this is a required by default when using the command line program
     /// i.e. a configuration file that's required for the program to function
     /// **NOTE:** required by default means, it is required *until* mutually
@@ -17,16 +22,67 @@ pub struct PosBuilder<'n> {
     /// A list of names of other arguments that are *required* to be used when 
     /// this flag is used
     pub requires: Option<HashSet<&'n str>>,
+    /// The groups this argument belongs to, whether set with `Arg::group()` or
+    /// `ArgGroup::args()`
+    pub groups: Vec<&'n str>,
+    /// A list of names of other arguments which, when used, make this argument no longer required
+    pub r_unless: Option<Vec<&'n str>>,
+    /// Whether all of the arguments in `r_unless` must be used, instead of any of them
+    pub r_unless_all: bool,
+    /// A list of other arguments and values which make this argument required
+    pub r_ifs: Option<Vec<(&'n str, &'n str)>>,
+    /// A list of values and other arguments which are required when this argument has the value
+    pub requires_ifs: Option<Vec<(&'n str, &'n str)>>,
     /// A list of names for other arguments that *may not* be used with this flag
     pub blacklist: Option<HashSet<&'n str>>,
+    /// A list of names for other arguments which this argument overrides, whichever is used last
+    /// wins
+    pub overrides: Option<Vec<&'n str>>,
+    /// Accept values starting with a `-`
+    pub allow_hyphen_vals: bool,
+    /// Only populated from the arguments following `--`
+    pub last: bool,
     /// A list of possible values for this argument
     pub possible_vals: Option<BTreeSet<&'n str>>,
+    /// The value used when this argument isn't supplied at runtime
+    pub default_val: Option<&'n OsStr>,
+    /// A list of other arguments, optional values, and the value used when this argument isn't
+    /// supplied but the other argument was
+    pub default_val_ifs: Option<Vec<(&'n str, Option<&'n str>, &'n str)>>,
+    /// The minimum number of values required in total
+    pub min_vals: Option<u8>,
+    /// The maximum number of values accepted in total
+    pub max_vals: Option<u8>,
     /// The index of the argument
-    pub index: u8 
+    pub index: u8,
+    /// The name displayed in the usage and help information instead of the argument's name
+    pub val_name: Option<&'n str>,
+    /// The kind of value accepted, used by shell completions
+    pub value_hint: Option<ValueHint>,
+    /// The function listing the candidate values at runtime, used by shell completions
+    pub candidates: Option<fn() -> Vec<String>>,
+    /// A custom message displayed when this argument is missing or has an invalid value
+    pub error_message: Option<&'n str>,
+    /// The spelling of the argument used in error messages
+    pub disp_name: Option<&'n str>,
+    /// A note on what to use instead, if the argument is deprecated
+    pub deprecated: Option<&'n str>,
+    /// Whether the argument is omitted from the help information
+    pub hidden: bool,
+    /// The heading of the help section the argument is listed in, if not the default one
+    pub help_heading: Option<&'n str>,
+}
+
+impl<'n> PosBuilder<'n> {
+    /// The name displayed in the usage and help information, which is the value name if there
+    /// is one
+    pub fn usage_name(&self) -> &'n str {
+        self.val_name.unwrap_or(self.name)
+    }
 }
 
 impl<'n> Display for PosBuilder<'n> {
     fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "{}{}{}{}", if self.required { "<" } else {"["}, self.name,if self.required { ">" } else {"]"}, if self.multiple {"..."}else{""})
+        write!(f, "{}{}{}{}", if self.required { "<" } else {"["}, self.usage_name(),if self.required { ">" } else {"]"}, if self.multiple {"..."}else{""})
     }
 }
\ No newline at end of file
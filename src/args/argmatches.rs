@@ -1,4 +1,13 @@
 use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::borrow::Cow;
+use std::slice;
+use std::process;
+#[cfg(feature = "serde")]
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::ser::{Serialize, Serializer, SerializeStruct};
 
 use args::SubCommand;
 use args::MatchedArg;
@@ -52,13 +61,43 @@ use args::MatchedArg;
 ///         println!("Not printing testing lists...");
 ///     }
 /// }
+/// ```
+///
+/// With the `serde` cargo feature `ArgMatches` implements `serde::Serialize`, so the matches can
+/// be logged or sent to another process. They're serialized as a struct with the `args` (a map
+/// from the name of each argument which is present to its `occurrences`, `indices`, `values`
+/// (`null` for flags, and invalid UTF-8 replaced by `U+FFFD`), and whether they're the `default`
+/// values) and the `subcommand` (its `name` and `matches`, or `null`).
+///
+/// ```ignore
+/// # use clap::App;
+/// let matches = App::new("MyApp").get_matches();
+/// println!("{}", serde_json::to_string(&matches).unwrap());
+/// ```
 pub struct ArgMatches<'n, 'a> {
     #[doc(hidden)]
     pub args: HashMap<&'a str, MatchedArg>,
     #[doc(hidden)]
+    pub groups: HashMap<&'a str, &'a str>,
+    #[doc(hidden)]
     pub subcommand: Option<Box<SubCommand<'n, 'a>>>,
     #[doc(hidden)]
-    pub usage: Option<String>
+    pub usage: Option<String>,
+    #[doc(hidden)]
+    pub trailing_sep: bool,
+    #[doc(hidden)]
+    pub raw_args: Vec<OsString>,
+    #[doc(hidden)]
+    pub bin_name: Option<String>,
+    // The placeholder and help of each argument with values, for errors about the values
+    #[doc(hidden)]
+    pub arg_help: HashMap<&'a str, (String, Option<&'a str>)>,
+    // Where errors are reported and the status code to exit with, as set on the App, so errors
+    // from value_t_or_exit! are reported like any other
+    #[doc(hidden)]
+    pub error_output: Option<fn(&str)>,
+    #[doc(hidden)]
+    pub error_code: Option<u8>
 }
 
 impl<'n, 'a> ArgMatches<'n, 'a> {
@@ -75,8 +114,15 @@ impl<'n, 'a> ArgMatches<'n, 'a> {
     pub fn new() -> ArgMatches<'n, 'a> {
         ArgMatches {
             args: HashMap::new(),
+            groups: HashMap::new(),
             subcommand: None,
-            usage: None
+            usage: None,
+            trailing_sep: false,
+            raw_args: vec![],
+            bin_name: None,
+            arg_help: HashMap::new(),
+            error_output: None,
+            error_code: None
         }
     }
 
@@ -87,6 +133,9 @@ impl<'n, 'a> ArgMatches<'n, 'a> {
     /// *NOTE:* If getting a value for an option or positional argument that allows multiples,
     /// prefer `values_of()` as `value_of()` will only return the _*first*_ value.
     ///
+    /// *NOTE:* This panics if the value isn't valid UTF-8, which is only possible when parsing
+    /// with `get_matches_from_os()` (use `value_of_os()` or `value_of_lossy()` instead)
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -100,16 +149,66 @@ impl<'n, 'a> ArgMatches<'n, 'a> {
         if let Some(ref arg) = self.args.get(name) {
             if let Some(ref vals) = arg.values {
                 if let Some(ref val) = vals.iter().nth(0) {
-                    return Some(&val[..]);
+                    return Some(utf8(name, val));
                 }
             }
         }
         None
     }
 
-    /// Gets the values of a specific option or positional argument in a vector (i.e. an argument
-    /// that takes multiple values at runtime). If the option wasn't present at runtime it
-    /// returns `None`
+    /// Gets the value of a specific option or positional argument as it was supplied, which may
+    /// not be valid UTF-8 (see `App::get_matches_from_os()`). If the option wasn't present at
+    /// runtime it returns `None`.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// use std::env;
+    /// use std::path::Path;
+    ///
+    /// let matches = App::new("myapp")
+    ///                   .arg(Arg::with_name("file").index(1))
+    ///                   .get_matches_from_os(env::args_os());
+    /// if let Some(f) = matches.value_of_os("file") {
+    ///     println!("File exists: {}", Path::new(f).exists());
+    /// }
+    /// ```
+    pub fn value_of_os<'na>(&self, name: &'na str) -> Option<&OsStr> {
+        self.args.get(name).and_then(|a| a.values.as_ref()).and_then(|v| v.iter().nth(0)).map(|v| &v[..])
+    }
+
+    /// Gets the value of a specific option or positional argument, where any invalid UTF-8 is
+    /// replaced by `U+FFFD REPLACEMENT CHARACTER` (see `App::get_matches_from_os()`). This is
+    /// useful to display a value which may not be valid UTF-8. If the option wasn't present at
+    /// runtime it returns `None`.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// use std::env;
+    ///
+    /// let matches = App::new("myapp")
+    ///                   .arg(Arg::with_name("file").index(1))
+    ///                   .get_matches_from_os(env::args_os());
+    /// if let Some(f) = matches.value_of_lossy("file") {
+    ///     println!("Opening {}", f);
+    /// }
+    /// ```
+    pub fn value_of_lossy<'na>(&self, name: &'na str) -> Option<Cow<str>> {
+        self.value_of_os(name).map(|v| v.to_string_lossy())
+    }
+
+    /// Gets an iterator over the values of a specific option or positional argument (i.e. an
+    /// argument that takes multiple values at runtime), which borrows the values instead of
+    /// copying them. If the option wasn't present at runtime it returns `None`
+    ///
+    /// The values are guaranteed to be in the exact order they were supplied on the command line,
+    /// regardless of which form was used for each (i.e. `--opt=a --opt b -o c` returns
+    /// `["a", "b", "c"]`).
     ///
     /// # Example
     ///
@@ -118,22 +217,50 @@ impl<'n, 'a> ArgMatches<'n, 'a> {
     /// # let matches = App::new("myapp").arg(Arg::new("output").takes_value(true)).get_matches();
     /// // If the program had option "-c" that took a value and was run
     /// // via "myapp -o some -o other -o file"
-    /// // values_of() would return a [&str; 3] ("some", "other", "file")
+    /// // values_of() would yield "some", "other", and "file"
     /// if let Some(os) = matches.values_of("output") {
     ///        for o in os {
     ///            println!("A value for output: {}", o);
     ///        }
     /// }
     /// ```
-    pub fn values_of<'na>(&'a self, name: &'na str) -> Option<Vec<&'a str>> {
+    pub fn values_of<'na>(&'a self, name: &'na str) -> Option<Values<'a>> {
         if let Some(ref arg) = self.args.get(name) {
             if let Some(ref vals) = arg.values {
-                return Some(vals.iter().map(|s| &s[..]).collect::<Vec<_>>());
+                // Invalid UTF-8 is reported up front, so the iterator itself can't fail
+                for v in vals.iter() {
+                    utf8(name, v);
+                }
+                return Some(Values { iter: vals.iter() });
             }
         }
         None
     }
 
+    /// Gets an iterator over the values of a specific option or positional argument as they were
+    /// supplied, which may not be valid UTF-8 (see `App::get_matches_from_os()`). If the option
+    /// wasn't present at runtime it returns `None`
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// use std::env;
+    ///
+    /// let matches = App::new("myapp")
+    ///                   .arg(Arg::with_name("files").index(1).multiple(true))
+    ///                   .get_matches_from_os(env::args_os());
+    /// if let Some(fs) = matches.values_of_os("files") {
+    ///     for f in fs {
+    ///         println!("Opening {:?}", f);
+    ///     }
+    /// }
+    /// ```
+    pub fn values_of_os<'na>(&'a self, name: &'na str) -> Option<OsValues<'a>> {
+        self.args.get(name).and_then(|a| a.values.as_ref()).map(|v| OsValues { iter: v.iter() })
+    }
+
     /// Returns if an argument was present at runtime. 
     ///
     ///
@@ -158,6 +285,11 @@ impl<'n, 'a> ArgMatches<'n, 'a> {
     /// If an argument isn't present it will return `0`. Can be used on arguments which *don't*
     /// allow multiple occurrences, but will obviously only return `0` or `1`.
     ///
+    /// Flags and options count each time they're used, regardless of the form (i.e. `-vvv`,
+    /// `-v -v -v`, and `-v --verbose -v` are all `3`, and `-o a b` is `1` even if it consumes
+    /// multiple values), whereas each value of a positional argument counts as an occurrence.
+    /// Values from the environment or a default value don't count, so those return `0`.
+    ///
     ///
     /// # Example
     ///
@@ -170,13 +302,155 @@ impl<'n, 'a> ArgMatches<'n, 'a> {
     ///     println!("Debug mode kind of on");
     /// }
     /// ```
-    pub fn occurrences_of<'na>(&self, name: &'na str) -> u8 {
+    pub fn occurrences_of<'na>(&self, name: &'na str) -> u64 {
         if let Some(ref arg) = self.args.get(name) {
             return arg.occurrences;
         }
         0
     }
 
+    /// Returns the index at which an argument first appeared at runtime, counting the arguments
+    /// supplied from `1` (the binary name is `0`). For options and positional arguments this is
+    /// the index of their first value, and for flags the index of their first occurrence. Returns
+    /// `None` if the argument wasn't supplied, including when it only has a default value or a
+    /// value from the environment.
+    ///
+    /// **NOTE:** Values supplied within the same argument (i.e. `--opt=a,b`, or `-abc` for
+    /// flags) share its index
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myapp")
+    /// #     .arg(Arg::with_name("debug").short("d"))
+    /// #     .arg(Arg::with_name("output").short("o").takes_value(true))
+    /// #     .get_matches();
+    /// // If the program was run via "myapp -o out.txt -d"
+    /// if matches.index_of("debug") > matches.index_of("output") {
+    ///     println!("Debug mode was enabled after choosing the output");
+    /// }
+    /// ```
+    pub fn index_of<'na>(&self, name: &'na str) -> Option<usize> {
+        self.args.get(name).and_then(|a| a.indices.first().cloned())
+    }
+
+    /// Gets an iterator over the indices at which each value of an option or positional argument
+    /// (or each occurrence of a flag) appeared at runtime, in the order they were supplied (see
+    /// `index_of()`). This allows reconstructing the relative order of interleaved arguments,
+    /// such as a compiler's `-I` and `-L` options. Returns `None` if the argument wasn't supplied.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myapp")
+    /// #     .arg(Arg::with_name("include").short("I").takes_value(true).multiple(true))
+    /// #     .arg(Arg::with_name("lib").short("L").takes_value(true).multiple(true))
+    /// #     .get_matches();
+    /// // If the program was run via "myapp -I a -L b -I c", the indices of "include" are 2 and 6
+    /// let mut paths = vec![];
+    /// for name in ["include", "lib"].iter() {
+    ///     if let (Some(vals), Some(idxs)) = (matches.values_of(name), matches.indices_of(name)) {
+    ///         paths.extend(idxs.zip(vals).map(|(i, v)| (i, *name, v)));
+    ///     }
+    /// }
+    /// paths.sort();
+    /// ```
+    pub fn indices_of<'na>(&'a self, name: &'na str) -> Option<Indices<'a>> {
+        match self.args.get(name) {
+            Some(a) if !a.indices.is_empty() => Some(Indices { iter: a.indices.iter() }),
+            _                                => None
+        }
+    }
+
+    /// Returns `true` if the value of an option or positional argument is its default value
+    /// (set with `Arg::default_value()`), i.e. the user didn't supply the argument at runtime.
+    /// Returns `false` if the user supplied the argument, or if it wasn't present at all.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myapp")
+    /// #     .arg(Arg::with_name("config").short("c").takes_value(true).default_value("config.json"))
+    /// #     .get_matches();
+    /// if matches.is_default("config") {
+    ///     println!("Using the default config file");
+    /// }
+    /// ```
+    pub fn is_default<'na>(&self, name: &'na str) -> bool {
+        if let Some(ref arg) = self.args.get(name) {
+            return arg.default;
+        }
+        false
+    }
+
+    /// Returns the number of distinct arguments supplied at runtime, plus one if a subcommand
+    /// was used. Arguments which only have their default value, or a value read from the
+    /// environment, aren't counted since the user didn't supply them on the command line.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myapp").arg(Arg::with_name("file").index(1)).get_matches();
+    /// println!("{} arguments were used", matches.len());
+    /// ```
+    pub fn len(&self) -> usize {
+        self.args.values().filter(|a| !a.default && a.occurrences > 0).count() +
+            if self.subcommand.is_some() { 1 } else { 0 }
+    }
+
+    /// Returns `true` if nothing at all was supplied at runtime, neither arguments nor a
+    /// subcommand (see `len()`). This is useful to fall back to an interactive mode when the
+    /// program is run without any input.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myapp").arg(Arg::with_name("file").index(1)).get_matches();
+    /// if matches.is_empty() {
+    ///     println!("Entering interactive mode...");
+    /// }
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the name of the argument which was used at runtime out of a particular
+    /// `ArgGroup`, or `None` if no argument from the group was present. This is useful to
+    /// dispatch on which member of a group was used, without checking each with `is_present()`.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, ArgGroup};
+    /// # let matches = App::new("myapp")
+    /// #     .args_from_usage("--json 'json output'
+    /// #                       --yaml 'yaml output'")
+    /// #     .arg_group(ArgGroup::with_name("format").args(vec!["json", "yaml"]).required(true))
+    /// #     .get_matches();
+    /// match matches.group_value("format") {
+    ///     Some("json") => println!("Printing JSON..."),
+    ///     Some("yaml") => println!("Printing YAML..."),
+    ///     _            => unreachable!()
+    /// }
+    /// ```
+    pub fn group_value<'na>(&self, name: &'na str) -> Option<&str> {
+        if let Some(member) = self.groups.get(name) {
+            return Some(member);
+        }
+        None
+    }
+
     /// Returns the `ArgMatches` for a particular subcommand or None if the subcommand wasn't
     /// present at runtime.
     ///
@@ -262,4 +536,222 @@ impl<'n, 'a> ArgMatches<'n, 'a> {
         // Should be un-reachable
         ""
     }
-}
\ No newline at end of file
+
+    /// Returns the arguments exactly as they were supplied to the program, including the binary
+    /// name, before any parsing (i.e. `--opt=value` is a single argument). This is useful for
+    /// audit logging, or to show what the user literally typed when reporting an error. The
+    /// matches of a subcommand return the same arguments as those of its parent.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myapp").arg(Arg::with_name("cmd").multiple(true)).get_matches();
+    /// let typed = matches.raw_args().iter().map(|a| a.to_string_lossy()).collect::<Vec<_>>();
+    /// println!("Running: {}", typed.join(" "));
+    /// ```
+    pub fn raw_args(&self) -> &[OsString] {
+        &self.raw_args[..]
+    }
+
+    /// Returns the name of the binary as it was invoked, including the path of subcommands
+    /// leading to this `SubCommand` (i.e. `myprog test`), which is useful for error messages or
+    /// hints about re-running the program. Returns `None` if no binary name was available, i.e.
+    /// the arguments were empty.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myapp").get_matches();
+    /// if let Some(bin) = matches.bin_name() {
+    ///     println!("Try '{} --verbose' for more details", bin);
+    /// }
+    /// ```
+    pub fn bin_name(&self) -> Option<&str> {
+        self.bin_name.as_ref().map(|b| &b[..])
+    }
+
+    /// Records an occurrence of an argument along with its values (if any, flags have none) as if
+    /// it had been supplied at runtime, appending to any existing values. This is useful to merge
+    /// in settings from other sources such as a config file, which can then be checked with
+    /// `App::validate()`.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// let mut app = App::new("myprog")
+    ///                   .arg(Arg::with_name("config").long("config").takes_value(true));
+    /// # let mut matches = App::new("myprog").get_matches();
+    /// if !matches.is_present("config") {
+    ///     matches.add("config", vec!["default.conf"]);
+    /// }
+    /// ```
+    pub fn add<I, T>(&mut self, name: &'a str, vals: I) where I: IntoIterator<Item=T>, T: Into<OsString> {
+        let vals = vals.into_iter().map(|v| v.into()).collect::<Vec<OsString>>();
+        let ma = self.args.entry(name).or_insert(MatchedArg {
+            occurrences: 0,
+            indices: vec![],
+            values: None,
+            default: false
+        });
+        ma.occurrences += 1;
+        ma.default = false;
+        if !vals.is_empty() {
+            match ma.values {
+                Some(ref mut v) => v.extend(vals.into_iter()),
+                None            => ma.values = Some(vals)
+            }
+        }
+    }
+
+    /// Returns `true` if the `--` separator (which stops the parsing of flags and options) was
+    /// used at runtime for this `App` (or `SubCommand`), i.e. it distinguishes `myprog foo` from
+    /// `myprog -- foo`. This is useful when forwarding arguments to a child process unaltered.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// # let matches = App::new("myapp").arg(Arg::with_name("cmd").multiple(true)).get_matches();
+    /// if matches.trailing_separator_present() {
+    ///     println!("Everything after -- is passed to the child process as-is");
+    /// }
+    /// ```
+    pub fn trailing_separator_present(&self) -> bool {
+        self.trailing_sep
+    }
+
+    // Used by the value_t! macros to show the expected format and help of an argument when its
+    // value can't be parsed, i.e. "\n    --length <NUM>    The length to use"
+    #[doc(hidden)]
+    pub fn value_help<'na>(&self, name: &'na str) -> String {
+        match self.arg_help.get(name) {
+            Some(&(ref usage, help)) => format!("\n    {}{}", usage,
+                help.and_then(|h| h.lines().next()).map(|h| format!("    {}", h)).unwrap_or(String::new())),
+            None                     => String::new()
+        }
+    }
+
+    // Used by the value_t_or_exit! macro to report an error along with the usage statement on
+    // stderr (or to the function set with App::error_output()), and exit
+    #[doc(hidden)]
+    pub fn exit_with_error(&self, msg: &str) -> ! {
+        let out = format!("{}\n{}\nPlease re-run with --help for more information\n", msg, self.usage());
+        match self.error_output {
+            Some(f) => f(&out[..]),
+            None    => {
+                use std::io::{self, Write};
+                let _ = write!(io::stderr(), "{}", out);
+            }
+        }
+        process::exit(self.error_code.unwrap_or(1) as i32)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'n, 'a> Serialize for ArgMatches<'n, 'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let mut s = try!(serializer.serialize_struct("ArgMatches", 2));
+        // Sorted by name, so the output doesn't depend on the order of the HashMap
+        try!(s.serialize_field("args", &self.args.iter().collect::<BTreeMap<_, _>>()));
+        try!(s.serialize_field("subcommand", &self.subcommand));
+        s.end()
+    }
+}
+
+/// An iterator over the values of an argument, in the order they were supplied (see
+/// `ArgMatches::values_of()`)
+#[derive(Clone)]
+pub struct Values<'a> {
+    iter: slice::Iter<'a, OsString>
+}
+
+impl<'a> Iterator for Values<'a> {
+    type Item = &'a str;
+
+    // The values were checked to be valid UTF-8 by values_of()
+    fn next(&mut self) -> Option<&'a str> {
+        self.iter.next().map(|v| v.to_str().unwrap())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Values<'a> {
+    fn next_back(&mut self) -> Option<&'a str> {
+        self.iter.next_back().map(|v| v.to_str().unwrap())
+    }
+}
+
+impl<'a> ExactSizeIterator for Values<'a> {}
+
+/// An iterator over the values of an argument as they were supplied, which may not be valid
+/// UTF-8 (see `ArgMatches::values_of_os()`)
+#[derive(Clone)]
+pub struct OsValues<'a> {
+    iter: slice::Iter<'a, OsString>
+}
+
+impl<'a> Iterator for OsValues<'a> {
+    type Item = &'a OsStr;
+
+    fn next(&mut self) -> Option<&'a OsStr> {
+        self.iter.next().map(|v| &v[..])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for OsValues<'a> {
+    fn next_back(&mut self) -> Option<&'a OsStr> {
+        self.iter.next_back().map(|v| &v[..])
+    }
+}
+
+impl<'a> ExactSizeIterator for OsValues<'a> {}
+
+/// An iterator over the indices at which the values of an argument appeared (see
+/// `ArgMatches::indices_of()`)
+#[derive(Clone)]
+pub struct Indices<'a> {
+    iter: slice::Iter<'a, usize>
+}
+
+impl<'a> Iterator for Indices<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.iter.next().cloned()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Indices<'a> {
+    fn next_back(&mut self) -> Option<usize> {
+        self.iter.next_back().cloned()
+    }
+}
+
+impl<'a> ExactSizeIterator for Indices<'a> {}
+
+// Gets a value as a string slice, panicking if the value isn't valid UTF-8 since the accessors
+// returning strings can't report it otherwise
+fn utf8<'a>(name: &str, val: &'a OsStr) -> &'a str {
+    match val.to_str() {
+        Some(v) => v,
+        None    => panic!("The value of argument \"{}\" isn't valid UTF-8 ({:?})\n\n\tUse value_of_os() or value_of_lossy() to get values which may contain invalid UTF-8", name, val)
+    }
+}
@@ -1,6 +1,9 @@
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter, Result};
 
+#[cfg(feature = "yaml")]
+use yaml_rust::Yaml;
+
 /// `ArgGroup`s are a family of related arguments and way for you to say, "Any of these arguments".
 /// By placing arguments in a logical group, you can make easier requirement and exclusion rules
 /// intead of having to list each individually, or when you want a rule to apply "any but not all"
@@ -48,7 +51,13 @@ pub struct ArgGroup<'n, 'ar> {
     #[doc(hidden)]
     pub requires: Option<HashSet<&'ar str>>,
     #[doc(hidden)]
-    pub conflicts: Option<HashSet<&'ar str>>
+    pub conflicts: Option<HashSet<&'ar str>>,
+    #[doc(hidden)]
+    pub in_usage: bool,
+    #[doc(hidden)]
+    pub heading: Option<&'n str>,
+    #[doc(hidden)]
+    pub about: Option<&'n str>
 }
 
 impl<'n, 'ar> ArgGroup<'n, 'ar> {
@@ -70,8 +79,63 @@ impl<'n, 'ar> ArgGroup<'n, 'ar> {
             required: false,
             args: HashSet::new(),
             requires: None,
-            conflicts: None
+            conflicts: None,
+            in_usage: false,
+            heading: None,
+            about: None
+        }
+    }
+
+    /// Creates a new instance of `ArgGroup` from a YAML hash with a single key, the name of the
+    /// group, whose value is a hash of the group's settings. The settings use the same names as
+    /// the builder methods (i.e. `args`, `required`, `in_usage`, `requires`, `conflicts_with`,
+    /// `heading`, and `about`).
+    ///
+    /// **NOTE:** This requires the `yaml` cargo feature
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # use clap::ArgGroup;
+    /// // In group.yml:
+    /// //
+    /// // vers:
+    /// //     args: [ver, major, minor, patch]
+    /// //     required: true
+    /// let yml = load_yaml!("group.yml");
+    /// let group = ArgGroup::from_yaml(yml);
+    /// ```
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(y: &'n Yaml) -> ArgGroup<'n, 'n> {
+        let (name, settings) = match y.as_hash().and_then(|h| if h.len() == 1 { h.iter().next() } else { None }) {
+            Some((k, v)) => (k.as_str().expect("ArgGroup name in YAML must be a string"), v),
+            None         => panic!("ArgGroup in YAML must be a hash with a single key (the name of the group)")
+        };
+        let mut g = ArgGroup::with_name(name);
+        let settings = match settings.as_hash() {
+            Some(h) => h,
+            None    => return g
+        };
+        for (k, v) in settings.iter() {
+            let k = k.as_str().expect("ArgGroup setting names in YAML must be strings");
+            let l = || v.as_vec()
+                        .unwrap_or_else(|| panic!("ArgGroup \"{}\" YAML setting \"{}\" must be a list", name, k))
+                        .iter()
+                        .map(|i| i.as_str().unwrap_or_else(|| panic!("ArgGroup \"{}\" YAML setting \"{}\" must be a list of strings", name, k)))
+                        .collect::<Vec<_>>();
+            g = match k {
+                "args"           => g.args(l()),
+                "requires"       => g.requires_all(l()),
+                "conflicts_with" => g.conflicts_with_all(l()),
+                "required"       => g.required(v.as_bool().unwrap_or_else(|| panic!("ArgGroup \"{}\" YAML setting \"required\" must be a bool", name))),
+                "in_usage"       => g.in_usage(v.as_bool().unwrap_or_else(|| panic!("ArgGroup \"{}\" YAML setting \"in_usage\" must be a bool", name))),
+                "heading"        => g.heading(v.as_str().unwrap_or_else(|| panic!("ArgGroup \"{}\" YAML setting \"heading\" must be a string", name))),
+                "about"          => g.about(v.as_str().unwrap_or_else(|| panic!("ArgGroup \"{}\" YAML setting \"about\" must be a string", name))),
+                _                => panic!("Unknown setting \"{}\" for group \"{}\" in YAML", k, name)
+            };
         }
+        g
     }
 
     /// Adds an argument to this group by name
@@ -110,6 +174,27 @@ impl<'n, 'ar> ArgGroup<'n, 'ar> {
         self
     }
 
+    /// Sets the arguments which belong to this group by name using a Vec. The arguments may be
+    /// added to the `App` before or after the group, and do not need to name the group themselves
+    /// via `Arg::group()`.
+    ///
+    /// **NOTE:** Every argument listed must exist in the `App` by the time parsing begins,
+    /// otherwise `clap` will `panic!` with a message naming the missing argument
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, ArgGroup};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg_group(
+    /// # ArgGroup::with_name("conifg")
+    /// .args(vec!["config", "input", "output"])
+    /// # ).get_matches();
+    pub fn args(self, ns: Vec<&'ar str>) -> ArgGroup<'n, 'ar> {
+        self.add_all(ns)
+    }
+
     /// Sets the requirement of this group. A required group will be displayed in the usage string
     /// of the application in the format `[arg|arg2|arg3]`. A required `ArgGroup` simply states
     /// that one, and only one argument from this group *must* be present at runtime (unless
@@ -130,6 +215,67 @@ impl<'n, 'ar> ArgGroup<'n, 'ar> {
         self
     }
 
+    /// Displays this group in the usage string even though it isn't required, i.e.
+    /// `myprog [FLAGS] [--major|--minor|--patch]`, to communicate that its arguments are
+    /// alternatives to one another. Required groups are always displayed.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, ArgGroup};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg_group(
+    /// # ArgGroup::with_name("vers")
+    /// .in_usage(true)
+    /// # ).get_matches();
+    pub fn in_usage(mut self, u: bool) -> ArgGroup<'n, 'ar> {
+        self.in_usage = u;
+        self
+    }
+
+    /// Lists the arguments of this group in their own section of the help information with the
+    /// given title, instead of in the default `FLAGS`, `OPTIONS`, and `POSITIONAL ARGUMENTS`
+    /// sections. This way related arguments are displayed together, whether or not the group is
+    /// used for any requirement or exclusion rules.
+    ///
+    /// **NOTE:** An argument belonging to multiple groups with a heading is only listed in the
+    /// first one (ordered by heading)
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, ArgGroup};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg_group(
+    /// # ArgGroup::with_name("vers")
+    /// .heading("VERSIONING")
+    /// # ).get_matches();
+    pub fn heading(mut self, h: &'n str) -> ArgGroup<'n, 'ar> {
+        self.heading = Some(h);
+        self
+    }
+
+    /// Sets a description displayed at the top of this group's section of the help information
+    /// (see `heading()`)
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, ArgGroup};
+    /// # let matches = App::new("myprog")
+    /// #                 .arg_group(
+    /// # ArgGroup::with_name("vers")
+    /// .heading("VERSIONING")
+    /// .about("Sets the version of the release, either manually or by increasing a part")
+    /// # ).get_matches();
+    pub fn about(mut self, a: &'n str) -> ArgGroup<'n, 'ar> {
+        self.about = Some(a);
+        self
+    }
+
     /// Sets the requirement rules of this group. This is not to be confused with a required group.
     /// Requirement rules function just like argument requirement rules, you can name other
     /// arguments or groups that must be present when one of the arguments from this group is used.
@@ -239,7 +385,10 @@ impl<'n, 'ar> Debug for ArgGroup<'n, 'ar> {
             required: {:?},
             requires: {:?},
             conflicts: {:?},
-}}", self.name, self.args, self.required, self.requires, self.conflicts)
+            in_usage: {:?},
+            heading: {:?},
+            about: {:?},
+}}", self.name, self.args, self.required, self.requires, self.conflicts, self.in_usage, self.heading, self.about)
     }
 }
 
@@ -1,9 +1,33 @@
+use std::ffi::OsString;
+
+#[cfg(feature = "serde")]
+use serde::ser::{Serialize, Serializer, SerializeStruct};
+
 #[doc(hidden)]
 pub struct MatchedArg {
 	// #[doc(hidden)]
     // pub name: String,
 	#[doc(hidden)]
-    pub occurrences: u8,
+    pub occurrences: u64,
+	// The index of each value (or occurrence of a flag) among the arguments supplied
 	#[doc(hidden)]
-    pub values: Option<Vec<String>> 
-}
\ No newline at end of file
+    pub indices: Vec<usize>,
+	#[doc(hidden)]
+    pub values: Option<Vec<OsString>>,
+	// Whether the values are the argument's default value, instead of supplied by the user
+	#[doc(hidden)]
+    pub default: bool
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for MatchedArg {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let mut s = try!(serializer.serialize_struct("MatchedArg", 4));
+        try!(s.serialize_field("occurrences", &self.occurrences));
+        try!(s.serialize_field("indices", &self.indices));
+        // Values which aren't valid UTF-8 can't be represented as strings by most formats
+        try!(s.serialize_field("values", &self.values.as_ref().map(|vals| vals.iter().map(|v| v.to_string_lossy()).collect::<Vec<_>>())));
+        try!(s.serialize_field("default", &self.default));
+        s.end()
+    }
+}
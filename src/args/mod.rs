@@ -1,13 +1,15 @@
-pub use self::arg::Arg;
-pub use self::argmatches::ArgMatches;
+pub use self::arg::{Arg, ArgSettings};
+pub use self::argmatches::{ArgMatches, Values, OsValues, Indices};
 pub use self::subcommand::SubCommand;
 pub use self::argbuilder::{FlagBuilder, OptBuilder, PosBuilder};
 pub use self::matchedarg::MatchedArg;
 pub use self::group::ArgGroup;
+pub use self::valuehint::ValueHint;
 
 mod arg;
 mod argmatches;
 mod subcommand;
 mod argbuilder;
 mod matchedarg;
-mod group;
\ No newline at end of file
+mod group;
+mod valuehint;
\ No newline at end of file
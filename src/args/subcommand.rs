@@ -1,6 +1,11 @@
+use std::borrow::Cow;
+
 use App;
 use ArgMatches;
 
+#[cfg(feature = "serde")]
+use serde::ser::{Serialize, Serializer, SerializeStruct};
+
 /// The abstract representation of a command line subcommand used by the consumer of the library.
 /// 
 ///
@@ -21,7 +26,8 @@ use ArgMatches;
 ///                           .index(1))
 /// # ).get_matches();
 pub struct SubCommand<'n, 'a> {
-       pub name: &'n str,
+       // Owned only for external subcommands, whose names come from the command line
+       pub name: Cow<'n, str>,
        pub matches: ArgMatches<'n, 'a>
 }
 
@@ -40,4 +46,48 @@ impl<'n, 'a> SubCommand<'n, 'a> {
     pub fn new<'au, 'v, 'ab, 'u, 'h, 'ar>(name: &'ar str) -> App<'au, 'v, 'ab, 'u, 'h, 'ar> {
         App::new(name)
     }
-}
\ No newline at end of file
+
+    /// Creates a new instance of a subcommand with a name and a short description, which is the
+    /// most common case. This is a shorthand for `SubCommand::new(name).about(about)`, and the
+    /// returned value can be further configured like any other subcommand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, SubCommand};
+    /// # let prog = App::new("myprog").subcommand(
+    /// SubCommand::with_about("config", "Used for configuration")
+    /// # ).get_matches();
+    /// ```
+    pub fn with_about<'au, 'v, 'ab, 'u, 'h, 'ar>(name: &'ar str, about: &'ab str) -> App<'au, 'v, 'ab, 'u, 'h, 'ar> {
+        App::new(name).about(about)
+    }
+
+    /// Creates a new instance of a subcommand with a name, a short description, and arguments
+    /// defined by a usage string (one argument per line, see `App::args_from_usage()`). This is a
+    /// shorthand for `SubCommand::new(name).about(about).args_from_usage(usage)`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, SubCommand};
+    /// # let prog = App::new("myprog").subcommand(
+    /// SubCommand::with_usage("config", "Used for configuration",
+    ///                        "-d --debug 'Prints debug information'
+    ///                         <file> 'The configuration file to use'")
+    /// # ).get_matches();
+    /// ```
+    pub fn with_usage<'au, 'v, 'ab, 'u, 'h, 'ar>(name: &'ar str, about: &'ab str, usage: &'ar str) -> App<'au, 'v, 'ab, 'u, 'h, 'ar> {
+        App::new(name).about(about).args_from_usage(usage)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'n, 'a> Serialize for SubCommand<'n, 'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let mut s = try!(serializer.serialize_struct("SubCommand", 2));
+        try!(s.serialize_field("name", &self.name));
+        try!(s.serialize_field("matches", &self.matches));
+        s.end()
+    }
+}
@@ -0,0 +1,59 @@
+use std::str::FromStr;
+
+/// Describes the kind of value an option or positional argument accepts (see
+/// `Arg::value_hint()`), so shell completion scripts can offer suitable candidates, i.e.
+/// complete file paths for a `FilePath` instead of falling back to all words.
+///
+///
+/// # Example
+///
+/// ```no_run
+/// # use clap::{App, Arg, ValueHint};
+/// let matches = App::new("myprog")
+///                   .arg(Arg::with_name("input")
+///                            .index(1)
+///                            .value_name("FILE")
+///                            .value_hint(ValueHint::FilePath))
+///                   .get_matches();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueHint {
+    /// Any path to an existing file or directory
+    AnyPath,
+    /// A path to a file
+    FilePath,
+    /// A path to a directory
+    DirPath,
+    /// A path to an executable file
+    ExecutablePath,
+    /// The name of a command found in the `PATH`
+    CommandName,
+    /// The name of a user of the system
+    Username,
+    /// The name of a host
+    Hostname,
+    /// A URL
+    Url,
+    /// Anything else, so no candidates are offered
+    Other
+}
+
+impl FromStr for ValueHint {
+    type Err = String;
+
+    // The names are matched case insensitively, and may be written in snake_case (i.e. in YAML)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.replace("_", "").to_lowercase()[..] {
+            "anypath"        => Ok(ValueHint::AnyPath),
+            "filepath"       => Ok(ValueHint::FilePath),
+            "dirpath"        => Ok(ValueHint::DirPath),
+            "executablepath" => Ok(ValueHint::ExecutablePath),
+            "commandname"    => Ok(ValueHint::CommandName),
+            "username"       => Ok(ValueHint::Username),
+            "hostname"       => Ok(ValueHint::Hostname),
+            "url"            => Ok(ValueHint::Url),
+            "other"          => Ok(ValueHint::Other),
+            _                => Err(format!("{} isn't a valid value hint", s))
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Generates a commented configuration file template (in TOML format) from an `App` definition
+//!
+//! This allows applications which also read their settings from a configuration file to ship a
+//! template derived from the same source as the command line arguments, so the two never drift
+//! apart.
+//!
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use clap::App;
+//! use std::fs::File;
+//!
+//! let app = App::new("myprog")
+//!                .version("1.0")
+//!                .about("Does awesome things")
+//!                .arg_from_usage("-c --color=[WHEN] 'Sets when to use colors'");
+//! let mut f = File::create("myprog.toml").unwrap();
+//! clap::config::generate(&app, &mut f).unwrap();
+//! ```
+use std::io::{self, Write};
+
+use app::App;
+
+/// Renders a configuration file template for `app` in TOML format into `w`. Every flag and option
+/// is listed with its help text, possible values, and default value, as a commented out key (the
+/// long version of the argument, or its name if it has none) so the template has no effect until
+/// it's edited. Flags are booleans (or counts, if they may be used multiple times) and options
+/// which accept multiple values are arrays. The arguments of each subcommand are listed in a table
+/// named after the subcommand.
+///
+/// Positional arguments, hidden subcommands, and the automatically generated `help` and
+/// `version` flags are omitted.
+///
+///
+/// # Example
+///
+/// ```no_run
+/// # use clap::App;
+/// let app = App::new("myprog");
+/// clap::config::generate(&app, &mut std::io::stdout()).unwrap();
+/// ```
+pub fn generate<W: Write>(app: &App, w: &mut W) -> io::Result<()> {
+    try!(writeln!(w, "# Configuration for {}", format!("{} {}", app.name, app.version.unwrap_or("")).trim()));
+    if let Some(about) = app.about {
+        try!(write_comment(w, about));
+    }
+    write_args(app, "", w)
+}
+
+// Writes the keys of the flags and options of an app, followed by a table for each of its
+// subcommands (recursively), where prefix is the name of the table of the app
+fn write_args<W: Write>(app: &App, prefix: &str, w: &mut W) -> io::Result<()> {
+    for f in app.flags.values() {
+        try!(write_key(w, f.long.unwrap_or(f.name), f.long_help.or(f.help), &deprecated_note!(f)[..],
+            if f.multiple { "0" } else { "false" }));
+    }
+    for o in app.opts.values().filter(|o| !o.env_only()) {
+        let note = match o.possible_vals {
+            Some(ref p) => format!("{}\nPossible values: {}", deprecated_note!(o),
+                                   p.iter().map(|v| &v[..]).collect::<Vec<_>>().join(", ")),
+            None        => deprecated_note!(o)
+        };
+        let default = o.default_val.map(|d| string(&d.to_string_lossy()));
+        let value = if o.multiple || o.multiple_vals {
+            format!("[{}]", default.unwrap_or(String::new()))
+        } else {
+            default.unwrap_or("\"\"".to_owned())
+        };
+        try!(write_key(w, o.long.unwrap_or(o.name), o.long_help.or(o.help), &note[..], &value[..]));
+    }
+
+    for sc in app.subcommands.values().filter(|sc| !sc.hidden) {
+        let table = if prefix.is_empty() { key(&sc.name[..]) } else { format!("{}.{}", prefix, key(&sc.name[..])) };
+        try!(writeln!(w, ""));
+        try!(writeln!(w, "[{}]", table));
+        if let Some(about) = sc.about {
+            try!(write_comment(w, about));
+        }
+        try!(write_args(sc, &table[..], w));
+    }
+    Ok(())
+}
+
+// Writes a single commented out key with its help text and any note (such as the possible
+// values) as comments above it
+fn write_key<W: Write>(w: &mut W, name: &str, help: Option<&str>, note: &str, value: &str) -> io::Result<()> {
+    try!(writeln!(w, ""));
+    let text = format!("{}{}", help.unwrap_or(""), note);
+    if !text.trim().is_empty() {
+        try!(write_comment(w, text.trim()));
+    }
+    writeln!(w, "#{} = {}", key(name), value)
+}
+
+// Writes (possibly multi-line) text as comments
+fn write_comment<W: Write>(w: &mut W, text: &str) -> io::Result<()> {
+    for line in text.lines() {
+        try!(writeln!(w, "{}", format!("# {}", line).trim_right()));
+    }
+    Ok(())
+}
+
+// Formats a key, which is only quoted if it contains characters not allowed in bare keys
+fn key(k: &str) -> String {
+    if !k.is_empty() && k.chars().all(|c| (c.is_alphanumeric() && (c as u32) < 0x80) || c == '-' || c == '_') {
+        k.to_owned()
+    } else {
+        string(k)
+    }
+}
+
+// Formats a basic string, escaping quotes, backslashes, and control characters
+fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"'  => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04X}", c as u32)[..]),
+            c    => out.push(c)
+        }
+    }
+    out.push('"');
+    out
+}
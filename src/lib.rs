@@ -322,18 +322,52 @@
 //! 
 //! `clap` is licensed under the MIT license. Please the LICENSE-MIT file in this repository for more information.
 
-pub use args::{Arg, SubCommand, ArgMatches, ArgGroup};
-pub use app::App;
+#[cfg(feature = "yaml")]
+extern crate yaml_rust;
+#[cfg(feature = "serde")]
+extern crate serde;
+
+pub use args::{Arg, ArgSettings, SubCommand, ArgMatches, ArgGroup, ValueHint, Values, OsValues, Indices};
+pub use app::{App, AppSettings, ParentUsage};
+#[doc(hidden)]
+pub use app::{split_authors, assert_unique_subcommands};
+pub use partial::{PartialParser, Expected};
+#[cfg(feature = "yaml")]
+pub use yaml_rust::YamlLoader;
 
 #[macro_use]
 mod macros;
 mod app;
 mod args;
 mod usageparser;
+mod suggestions;
+mod quote;
+mod osstr;
+mod partial;
+pub mod man;
+pub mod config;
+#[cfg(feature = "timings")]
+pub mod timings;
 
 #[cfg(test)]
 mod tests {
-    use super::{App, Arg, SubCommand};
+    use super::{App, AppSettings, Arg, ArgGroup, ArgMatches, SubCommand, Expected};
+    use super::man;
+    use super::config;
+    use super::suggestions;
+    use super::quote;
+    use std::ffi::OsString;
+
+	// The arguments to parse, starting with the name of the binary
+	fn os_args(args: &[&str]) -> Vec<OsString> {
+	    args.iter().map(|a| OsString::from(*a)).collect()
+	}
+
+	// Used with App::error_output() so a test can expect an error by expecting a panic, instead
+	// of the process exiting
+	fn panic_on_error(e: &str) {
+	    panic!("{}", e);
+	}
 
     #[test]
 	fn create_app() {
@@ -993,4 +1027,601 @@ mod tests {
 	        Arg::new("arg2").long("long")
 	    ]);
 	}
-}
+
+	#[test]
+	fn generate_man_page() {
+	    let app = App::new("myprog")
+	                .version("1.0")
+	                .about("does awesome things")
+	                .arg_from_usage("-c --config=[CONFIG] 'sets a config file'")
+	                .arg_from_usage("[input] 'the input file'")
+	                .subcommand(SubCommand::new("test").about("tests things"));
+	    let mut out = vec![];
+	    man::generate(&app, &mut out).unwrap();
+	    let page = String::from_utf8(out).unwrap();
+	    assert!(page.contains(".SH NAME\nmyprog \\- does awesome things\n"));
+	    assert!(page.contains(".SH SYNOPSIS\nmyprog [FLAGS] [OPTIONS] [POSITIONAL] [SUBCOMMANDS]\n"));
+	    assert!(page.contains(".TP\n\\fB\\-c, \\-\\-config <CONFIG>\\fR\nsets a config file\n"));
+	    assert!(page.contains(".TP\n\\fB\\-h, \\-\\-help\\fR\nPrints help information\n"));
+	    assert!(page.contains(".SH ARGS\n.TP\n\\fB[input]\\fR\nthe input file\n"));
+	    assert!(page.contains(".SH SUBCOMMANDS\n.TP\n\\fBtest\\fR\ntests things\n"));
+	}
+
+	#[test]
+	fn suggest_closest_value() {
+	    assert_eq!(suggestions::levenshtein("verbse", "verbose"), 1);
+	    assert_eq!(suggestions::levenshtein("", "abc"), 3);
+	    assert_eq!(suggestions::did_you_mean("verbse", vec!["version", "verbose"]), Some("verbose"));
+	    assert_eq!(suggestions::did_you_mean("xyz", vec!["version", "verbose"]), None);
+	}
+
+	#[test]
+	#[cfg(feature = "yaml")]
+	fn create_app_from_yaml() {
+	    let yml = load_yaml!("../examples/15_FromYaml.yml");
+	    let app = App::from_yaml(yml);
+	    assert_eq!(app.name, "yml_app");
+	    assert_eq!(app.version.unwrap(), "1.0");
+	    assert!(app.opts.contains_key("config"));
+	    assert!(app.flags.contains_key("verbose"));
+	    assert_eq!(app.positionals_idx.get(&1).unwrap().name, "input");
+	    assert!(app.subcommands.get("test").unwrap().flags.contains_key("list"));
+	}
+
+	#[test]
+	fn group_args_added_without_group() {
+	    let _ = App::new("group")
+	                .arg_group(ArgGroup::with_name("vers").args(vec!["major", "minor"]))
+	                .args_from_usage("--major 'auto increase major'
+	                                  --minor 'auto increase minor'")
+	                .get_matches();
+	}
+
+	#[test]
+	#[should_panic]
+	fn group_with_missing_arg() {
+	    let _ = App::new("group")
+	                .arg_from_usage("--major 'auto increase major'")
+	                .arg_group(ArgGroup::with_name("vers").args(vec!["major", "mnior"]))
+	                .get_matches();
+	}
+
+	#[test]
+	fn quote_values_for_shell() {
+	    assert_eq!(quote::quote("fast"), "'fast'");
+	    assert_eq!(quote::quote("it's here"), "'it'\\''s here'");
+	    assert_eq!(quote::quote("a\nb\x1b[0m"), "$'a\\nb\\x1b[0m'");
+	}
+
+	#[test]
+	fn visible_alias_in_usage() {
+	    let app = App::new("visible_alias")
+	                  .arg(Arg::with_name("output").long("output").visible_alias("out").alias("o").takes_value(true));
+	    assert_eq!(format!("{}", app.opts["output"]), "--output|--out <output>");
+	}
+
+	#[test]
+	fn create_unicode_usage() {
+		let a = Arg::from_usage("[größe] -ä --größe... 'hilfe für größe'");
+		assert_eq!(a.name, "größe");
+		assert_eq!(a.short.unwrap(), 'ä');
+		assert_eq!(a.long.unwrap(), "größe");
+		assert_eq!(a.help.unwrap(), "hilfe für größe");
+		assert!(a.multiple);
+	}
+
+	#[test]
+	fn create_subcommand_with_usage() {
+	    let _ = App::new("test")
+	                .subcommands(vec![ SubCommand::with_about("some", "does some things"),
+	                                    SubCommand::with_usage("add", "adds things",
+	                                        "-r --roster 'the roster'
+	                                         [name] 'the name'")])
+	                .get_matches();
+	}
+
+	#[test]
+	#[should_panic]
+	fn global_arg_conflicts_with_subcommand_arg() {
+	    let _ = App::new("test")
+	                .arg(Arg::with_name("config").short("c").global(true))
+	                .subcommand(SubCommand::new("some")
+	                                        .arg(Arg::with_name("cfg").short("c")))
+	                .get_matches();
+	}
+
+	#[test]
+	fn create_args_from_usage_with_blank_lines_and_comments() {
+	    let app = App::new("test")
+	                .args_from_usage("-c --config=[CONFIG] 'sets a config file'
+
+	                                  # positional arguments
+	                                  [input] 'the input file'
+	                                  ");
+	    assert!(app.opts.contains_key("CONFIG"));
+	    assert!(app.positionals_idx.values().any(|p| p.name == "input"));
+	}
+
+	#[test]
+	fn generate_config_template() {
+	    let app = App::new("myprog")
+	                .version("1.0")
+	                .arg(Arg::with_name("color").long("color").takes_value(true)
+	                                            .help("when to use colors")
+	                                            .possible_values(vec!["auto", "never"])
+	                                            .default_value("auto"))
+	                .arg_from_usage("-q --quiet 'be quiet'")
+	                .subcommand(SubCommand::new("test").about("tests things"));
+	    let mut out = vec![];
+	    config::generate(&app, &mut out).unwrap();
+	    let template = String::from_utf8(out).unwrap();
+	    assert!(template.starts_with("# Configuration for myprog 1.0\n"));
+	    assert!(template.contains("\n# be quiet\n#quiet = false\n"));
+	    assert!(template.contains("\n# when to use colors\n# Possible values: auto, never\n#color = \"auto\"\n"));
+	    assert!(template.contains("\n[test]\n# tests things\n"));
+	}
+
+	#[test]
+	fn create_option_with_env_only() {
+		let app = App::new("test")
+			.arg(Arg::with_name("token").env("CLAP_TEST_TOKEN"));
+		let o = app.opts.get("token").unwrap();
+		assert!(o.env_only());
+		assert_eq!(o.switch(), "$CLAP_TEST_TOKEN");
+		assert!(app.positionals_idx.is_empty());
+	}
+
+	#[test]
+	fn strict_app_ignores_aliases() {
+	    let app = App::new("strict")
+	                  .arg(Arg::with_name("output").long("output").visible_alias("out").takes_value(true))
+	                  .subcommand(SubCommand::new("sub")
+	                                  .arg(Arg::with_name("verbose").long("verbose").alias("verb")))
+	                  .strict(true)
+	                  .arg(Arg::with_name("input").long("input").alias("in").takes_value(true))
+	                  .subcommand(SubCommand::new("other")
+	                                  .arg(Arg::with_name("quiet").long("quiet").alias("q")));
+	    assert!(app.opts["output"].strict);
+	    assert!(app.opts["input"].strict);
+	    assert_eq!(format!("{}", app.opts["output"]), "--output <output>");
+	    assert!(app.subcommands["sub"].flags["verbose"].strict);
+	    assert!(app.subcommands["other"].flags["quiet"].strict);
+	    let app = app.strict(false);
+	    assert_eq!(format!("{}", app.opts["output"]), "--output|--out <output>");
+	    assert!(!app.subcommands["other"].flags["quiet"].strict);
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn get_matches_from_os_keeps_invalid_utf8() {
+	    use std::ffi::{OsStr, OsString};
+	    use std::os::unix::ffi::OsStrExt;
+	    let bad = OsStr::from_bytes(b"f\xffo").to_os_string();
+	    let mut long = OsString::from("--out=");
+	    long.push(&bad);
+	    let m = App::new("myprog")
+	                .arg(Arg::with_name("out").long("out").takes_value(true))
+	                .arg(Arg::with_name("file").index(1))
+	                .get_matches_from_os(vec![OsString::from("myprog"), long, bad.clone()]);
+	    assert_eq!(m.value_of_os("out"), Some(&bad[..]));
+	    assert_eq!(m.value_of_os("file"), Some(&bad[..]));
+	    assert_eq!(m.value_of_lossy("file").unwrap(), "f\u{FFFD}o");
+	}
+
+	#[test]
+	fn validate_constructed_matches() {
+	    let mut app = App::new("myprog")
+	                      .arg(Arg::with_name("config").long("config").takes_value(true).required(true))
+	                      .arg(Arg::with_name("debug").short("d").conflicts_with("quiet"))
+	                      .arg(Arg::with_name("quiet").short("q"));
+	    let mut m = ArgMatches::new();
+	    assert!(app.validate(&m).is_err());
+	    m.add("config", vec!["my.conf"]);
+	    assert!(app.validate(&m).is_ok());
+	    m.add("debug", Vec::<String>::new());
+	    m.add("quiet", Vec::<String>::new());
+	    assert!(app.validate(&m).is_err());
+	    assert_eq!(m.value_of("config"), Some("my.conf"));
+	}
+
+	#[test]
+	fn partial_parser() {
+	    let mut app = App::new("myprog")
+	                      .subcommand(SubCommand::new("remote")
+	                          .arg(Arg::with_name("name").long("name").takes_value(true).required(true))
+	                          .arg(Arg::with_name("url").index(1)));
+	    let mut p = app.partial_parser();
+	    assert_eq!(p.expected(), Expected::Argument);
+	    assert!(p.push("remote").is_ok());
+	    assert_eq!(p.subcommand(), Some("remote"));
+	    assert!(p.push("--nmae").is_err());
+	    assert!(p.push("--name").is_ok());
+	    assert_eq!(p.expected(), Expected::Value("name"));
+	    assert!(!p.is_complete());
+	    assert!(p.push("origin").is_ok());
+	    assert_eq!(p.expected(), Expected::Positional("url"));
+	    assert!(p.is_complete());
+	}
+
+	#[test]
+	fn infer_long_args_setting() {
+	    let m = App::new("myprog")
+	                .setting(AppSettings::InferLongArgs)
+	                .arg(Arg::with_name("verbose").long("verbose"))
+	                .get_matches_from_os(os_args(&["myprog", "--verb"]));
+	    assert!(m.is_present("verbose"));
+	}
+
+	#[test]
+	#[should_panic(expected = "'--verb' isn't valid")]
+	fn strict_ignores_infer_settings() {
+	    App::new("myprog")
+	        .error_output(panic_on_error)
+	        .strict(true)
+	        .setting(AppSettings::InferLongArgs)
+	        .arg(Arg::with_name("verbose").long("verbose"))
+	        .get_matches_from_os(os_args(&["myprog", "--verb"]));
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn default_value_os_not_utf8() {
+	    use std::ffi::OsStr;
+	    use std::os::unix::ffi::OsStrExt;
+	    let dv = OsStr::from_bytes(b"dir\xff");
+	    let m = App::new("myprog")
+	                .arg(Arg::with_name("dir").long("dir").takes_value(true).default_value_os(dv))
+	                .get_matches_from_os(os_args(&["myprog"]));
+	    assert_eq!(m.value_of_os("dir"), Some(dv));
+	    assert!(m.is_default("dir"));
+	}
+
+	#[test]
+	fn short_cluster_ending_in_option() {
+	    let m = App::new("myprog")
+	                .arg(Arg::with_name("verbose").short("v"))
+	                .arg(Arg::with_name("output").short("o").takes_value(true))
+	                .get_matches_from_os(os_args(&["myprog", "-vo", "out.txt"]));
+	    assert!(m.is_present("verbose"));
+	    assert_eq!(m.value_of("output"), Some("out.txt"));
+	}
+
+	#[test]
+	#[should_panic(expected = "Argument '-o' takes a value, so it can only be the last one in a cluster of shorts (found in '-of')")]
+	fn short_cluster_option_not_last() {
+	    App::new("myprog")
+	        .error_output(panic_on_error)
+	        .arg(Arg::with_name("force").short("f"))
+	        .arg(Arg::with_name("output").short("o").takes_value(true))
+	        .get_matches_from_os(os_args(&["myprog", "-of", "out.txt"]));
+	}
+
+	#[test]
+	#[should_panic(expected = "Did you mean -o?")]
+	fn short_hint_includes_options() {
+	    App::new("myprog")
+	        .error_output(panic_on_error)
+	        .arg(Arg::with_name("force").short("f"))
+	        .arg(Arg::with_name("output").short("o").takes_value(true))
+	        .get_matches_from_os(os_args(&["myprog", "-fO"]));
+	}
+
+	#[test]
+	fn values_of_command_line_order() {
+	    let m = App::new("myprog")
+	                .arg(Arg::with_name("opt")
+	                    .short("o")
+	                    .long("opt")
+	                    .takes_value(true)
+	                    .multiple(true)
+	                    .use_delimiter(true))
+	                .get_matches_from_os(os_args(&["myprog", "--opt=a,b", "--opt", "c", "-o", "d,e"]));
+	    assert_eq!(m.values_of("opt").unwrap().collect::<Vec<_>>(), ["a", "b", "c", "d", "e"]);
+	}
+
+	#[test]
+	#[should_panic(expected = "'--out' isn't valid")]
+	fn strict_rejects_aliases() {
+	    App::new("myprog")
+	        .error_output(panic_on_error)
+	        .strict(true)
+	        .arg(Arg::with_name("output").long("output").alias("out").takes_value(true))
+	        .get_matches_from_os(os_args(&["myprog", "--out", "a.txt"]));
+	}
+
+	#[test]
+	fn unset_strict_accepts_aliases_again() {
+	    let m = App::new("myprog")
+	                .setting(AppSettings::Strict)
+	                .arg(Arg::with_name("output").long("output").alias("out").takes_value(true))
+	                .unset_setting(AppSettings::Strict)
+	                .get_matches_from_os(os_args(&["myprog", "--out", "a.txt"]));
+	    assert_eq!(m.value_of("output"), Some("a.txt"));
+	}
+
+	#[test]
+	#[should_panic(expected = "warning: the argument --old is deprecated: use --new instead")]
+	fn deprecated_warning_goes_to_error_output() {
+	    App::new("myprog")
+	        .error_output(panic_on_error)
+	        .arg(Arg::with_name("old").long("old").deprecated("use --new instead"))
+	        .arg(Arg::with_name("new").long("new"))
+	        .get_matches_from_os(os_args(&["myprog", "--old"]));
+	}
+
+	#[test]
+	fn deprecated_argument_still_parses() {
+	    let m = App::new("myprog")
+	                .arg(Arg::with_name("old").long("old").deprecated(""))
+	                .get_matches_from_os(os_args(&["myprog", "--old"]));
+	    assert!(m.is_present("old"));
+	}
+
+	#[test]
+	fn help_template_substitutes_once() {
+	    let mut app = App::new("myprog")
+	                      .about("Prints {usage} {bin}")
+	                      .help_template("{bin}: {about} {unknown} {{version}}");
+	    let mut out = vec![];
+	    app.write_help(&mut out).unwrap();
+	    assert_eq!(String::from_utf8(out).unwrap(), "myprog: Prints {usage} {bin} {unknown} {}\n");
+	}
+
+	#[test]
+	#[should_panic(expected = "ArgGroup \"vers\" contains the argument \"mnior\"")]
+	fn nested_subcommand_group_with_missing_arg() {
+	    App::new("myprog")
+	        .subcommand(SubCommand::new("release")
+	            .subcommand(SubCommand::new("bump")
+	                .arg_from_usage("--major 'auto increase major'")
+	                .arg_group(ArgGroup::with_name("vers").args(vec!["major", "mnior"]))))
+	        .get_matches_from_os(os_args(&["myprog"]));
+	}
+
+	#[test]
+	fn group_membership_both_ways() {
+	    let app = App::new("myprog")
+	                  .arg(Arg::with_name("major").long("major").group("vers"))
+	                  .arg_group(ArgGroup::with_name("vers").args(vec!["minor", "patch"]))
+	                  .arg(Arg::with_name("minor").long("minor"))
+	                  .arg(Arg::with_name("patch").long("patch"));
+	    assert_eq!(app.flags["major"].groups, ["vers"]);
+	    assert_eq!(app.flags["minor"].groups, ["vers"]);
+	    assert_eq!(app.flags["patch"].groups, ["vers"]);
+	}
+
+	#[test]
+	#[should_panic(expected = "cannot be used with")]
+	fn group_args_conflict_with_each_other() {
+	    App::new("myprog")
+	        .error_output(panic_on_error)
+	        .arg(Arg::with_name("major").long("major"))
+	        .arg(Arg::with_name("minor").long("minor"))
+	        .arg_group(ArgGroup::with_name("vers").args(vec!["major", "minor"]))
+	        .get_matches_from_os(os_args(&["myprog", "--major", "--minor"]));
+	}
+
+	#[test]
+	fn global_arg_after_subcommand() {
+	    let m = App::new("myprog")
+	                .arg(Arg::with_name("verbose").long("verbose").global(true))
+	                .subcommand(SubCommand::new("test")
+	                    .subcommand(SubCommand::new("unit")))
+	                .get_matches_from_os(os_args(&["myprog", "test", "unit", "--verbose"]));
+	    assert!(!m.is_present("verbose"));
+	    let test = m.subcommand_matches("test").unwrap();
+	    assert!(test.subcommand_matches("unit").unwrap().is_present("verbose"));
+	}
+
+	#[test]
+	fn env_fallback_and_command_line() {
+	    use std::env;
+	    env::set_var("CLAP_TEST_ENV_FALLBACK", "from-env");
+	    let app = || App::new("myprog")
+	                      .arg(Arg::with_name("name").long("name").takes_value(true).env("CLAP_TEST_ENV_FALLBACK"));
+	    let m = app().get_matches_from_os(os_args(&["myprog"]));
+	    assert_eq!(m.value_of("name"), Some("from-env"));
+	    let m = app().get_matches_from_os(os_args(&["myprog", "--name", "from-args"]));
+	    assert_eq!(m.value_of("name"), Some("from-args"));
+	}
+
+	#[test]
+	fn overrides_with_last_wins() {
+	    let app = || App::new("myprog")
+	                      .arg(Arg::with_name("color").long("color"))
+	                      .arg(Arg::with_name("no-color").long("no-color").overrides_with("color"));
+	    let m = app().get_matches_from_os(os_args(&["myprog", "--color", "--no-color"]));
+	    assert!(m.is_present("no-color"));
+	    assert!(!m.is_present("color"));
+	    let m = app().get_matches_from_os(os_args(&["myprog", "--no-color", "--color"]));
+	    assert!(m.is_present("color"));
+	    assert!(!m.is_present("no-color"));
+	}
+
+	#[test]
+	fn trailing_var_arg_captures_flags() {
+	    let m = App::new("myprog")
+	                .trailing_var_arg(true)
+	                .arg(Arg::with_name("verbose").short("v"))
+	                .arg(Arg::with_name("cmd").index(1).multiple(true).required(true))
+	                .get_matches_from_os(os_args(&["myprog", "-v", "ls", "-v", "--all", "--"]));
+	    assert!(m.is_present("verbose"));
+	    assert_eq!(m.occurrences_of("verbose"), 1);
+	    assert_eq!(m.values_of("cmd").unwrap().collect::<Vec<_>>(), ["ls", "-v", "--all", "--"]);
+	}
+
+	#[test]
+	fn exit_code_on_error() {
+	    use std::env;
+	    use std::process::Command;
+	    // The error exits the process, so the test runs itself again to check the status code
+	    if env::var_os("CLAP_TEST_EXIT_CODE").is_some() {
+	        App::new("myprog")
+	            .exit_code_on_error(64)
+	            .get_matches_from_os(os_args(&["myprog", "--nope"]));
+	        return;
+	    }
+	    let out = Command::new(env::current_exe().unwrap())
+	                  .args(&["tests::exit_code_on_error", "--exact"])
+	                  .env("CLAP_TEST_EXIT_CODE", "1")
+	                  .output()
+	                  .unwrap();
+	    assert_eq!(out.status.code(), Some(64));
+	    assert!(String::from_utf8_lossy(&out.stderr).contains("'--nope' isn't valid"));
+	}
+
+	#[test]
+	#[should_panic(expected = "USAGE:\n    myprog sub")]
+	fn error_output_applies_to_subcommands() {
+	    App::new("myprog")
+	        .error_output(panic_on_error)
+	        .subcommand(SubCommand::new("sub"))
+	        .get_matches_from_os(os_args(&["myprog", "sub", "--nope"]));
+	}
+
+	#[test]
+	fn partial_parser_agrees_with_full_parse() {
+	    let app = || App::new("myprog")
+	                      .arg(Arg::with_name("verbose").short("v"))
+	                      .arg(Arg::with_name("output").short("o").long("output").takes_value(true))
+	                      .arg(Arg::with_name("input").index(1).required(true));
+	    let args = ["-vo", "out.txt", "in.txt"];
+	    let mut partial = app();
+	    {
+	        let mut p = partial.partial_parser();
+	        for a in args.iter() {
+	            assert!(p.push(a).is_ok());
+	        }
+	        assert!(p.is_complete());
+	    }
+	    let m = app().get_matches_from_os(os_args(&["myprog", "-vo", "out.txt", "in.txt"]));
+	    assert!(m.is_present("verbose"));
+	    assert_eq!(m.value_of("output"), Some("out.txt"));
+	    assert_eq!(m.value_of("input"), Some("in.txt"));
+	}
+
+	#[test]
+	#[cfg(unix)]
+	#[should_panic(expected = "isn't valid")]
+	fn invalid_utf8_before_equals() {
+	    use std::ffi::OsStr;
+	    use std::os::unix::ffi::OsStrExt;
+	    App::new("myprog")
+	        .error_output(panic_on_error)
+	        .arg(Arg::with_name("out").long("out").takes_value(true))
+	        .get_matches_from_os(vec![OsString::from("myprog"), OsStr::from_bytes(b"--\xff\xff\xffabc=x").to_os_string()]);
+	}
+
+	#[test]
+	fn help_lists_args_added_after_write_help() {
+	    use std::env;
+	    use std::process::Command;
+	    // Printing the help exits the process, so the test runs itself again to check the output
+	    if env::var_os("CLAP_TEST_HELP_CACHE").is_some() {
+	        let mut app = App::new("myprog").arg(Arg::with_name("a").long("aaa"));
+	        app.write_help(&mut vec![]).unwrap();
+	        app.arg(Arg::with_name("b").long("bbb"))
+	           .get_matches_from_os(os_args(&["myprog", "-h"]));
+	        return;
+	    }
+	    let out = Command::new(env::current_exe().unwrap())
+	                  .args(&["tests::help_lists_args_added_after_write_help", "--exact", "--nocapture"])
+	                  .env("CLAP_TEST_HELP_CACHE", "1")
+	                  .output()
+	                  .unwrap();
+	    assert!(String::from_utf8_lossy(&out.stdout).contains("--bbb"));
+	}
+
+	#[test]
+	fn partial_parser_attached_short_values() {
+	    let mut app = App::new("myprog")
+	                      .arg(Arg::with_name("verbose").short("v"))
+	                      .arg(Arg::with_name("o").short("o").takes_value(true));
+	    let mut p = app.partial_parser();
+	    assert_eq!(p.push("-ofile"), Err("Argument '-o' takes a value, so it can only be the last one in a cluster of shorts (found in '-ofile')".to_owned()));
+	    assert!(p.push("-o=val").is_err());
+	    assert_eq!(p.push("-x"), Err("Argument '-x' isn't valid".to_owned()));
+	    assert!(p.push("-vo").is_ok());
+	    assert_eq!(p.expected(), Expected::Value("o"));
+	}
+
+	#[test]
+	#[should_panic(expected = "Argument '-o' takes a value, so it can only be the last one in a cluster of shorts (found in '-o=val')")]
+	fn attached_short_value_with_equals() {
+	    App::new("myprog")
+	        .error_output(panic_on_error)
+	        .arg(Arg::with_name("o").short("o").takes_value(true))
+	        .get_matches_from_os(os_args(&["myprog", "-o=val"]));
+	}
+
+	#[test]
+	fn partial_parser_gapped_indexes() {
+	    let mut app = App::new("myprog")
+	                      .arg(Arg::with_name("input").index(1).required(true))
+	                      .arg(Arg::with_name("output").index(3));
+	    let mut p = app.partial_parser();
+	    assert_eq!(p.expected(), Expected::Positional("input"));
+	    assert!(p.push("in.txt").is_ok());
+	    assert!(p.is_complete());
+	    assert_eq!(p.expected(), Expected::Positional("output"));
+	    assert!(p.push("out.txt").is_ok());
+	    assert!(p.push("extra").is_err());
+	}
+
+	#[test]
+	fn arg_group_merges_into_group_named_by_arg() {
+	    let mut app = App::new("myprog")
+	                      .arg(Arg::with_name("major").long("major").group("vers"))
+	                      .arg(Arg::with_name("minor").long("minor").group("vers"))
+	                      .arg_group(ArgGroup::with_name("vers")
+	                                     .in_usage(true)
+	                                     .heading("VERSIONING")
+	                                     .about("Increases a part of the version"));
+	    let mut out = vec![];
+	    app.write_help(&mut out).unwrap();
+	    let help = String::from_utf8(out).unwrap();
+	    assert!(help.contains("[--major|--minor]"));
+	    assert!(help.contains("VERSIONING"));
+	    assert!(help.contains("Increases a part of the version"));
+	}
+
+	#[test]
+	#[should_panic(expected = "abc isn't a valid u32")]
+	fn value_t_or_exit_uses_error_output() {
+	    let m = App::new("myprog")
+	                .error_output(panic_on_error)
+	                .arg(Arg::with_name("len").long("len").takes_value(true))
+	                .get_matches_from_os(os_args(&["myprog", "--len", "abc"]));
+	    let _ = value_t_or_exit!(m.value_of("len"), u32);
+	}
+
+	#[test]
+	fn split_authors_keeps_urls() {
+	    assert_eq!(super::split_authors("Kevin <mailto:kbknapp@gmail.com>:Bob (https://bob.example):"),
+	               ["Kevin <mailto:kbknapp@gmail.com>", "Bob (https://bob.example)"]);
+	    let _: String = crate_authors!(", ");
+	}
+
+	#[test]
+	fn version_template_substitutes_once() {
+	    let app = App::new("myprog")
+	                  .version("{bin}-1.0")
+	                  .git_hash("{version}")
+	                  .version_template("{bin} {version} ({hash}) {unknown}");
+	    let mut out = vec![];
+	    app.write_version(&mut out).unwrap();
+	    assert_eq!(String::from_utf8(out).unwrap(), "myprog {bin}-1.0 ({version}) {unknown}\n");
+	}
+
+	#[test]
+	fn man_page_lists_auto_flags_once() {
+	    let mut app = App::new("myprog").version("1.0");
+	    app.write_help(&mut vec![]).unwrap();
+	    let mut out = vec![];
+	    man::generate(&app, &mut out).unwrap();
+	    let page = String::from_utf8(out).unwrap();
+	    assert!(page.contains(".SH SYNOPSIS\nmyprog [FLAGS]\n"));
+	    assert_eq!(page.matches("\\-\\-help").count(), 1);
+	    assert_eq!(page.matches("\\-\\-version").count(), 1);
+	}
+}
\ No newline at end of file
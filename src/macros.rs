@@ -1,12 +1,15 @@
 // De-duplication macro used in src/app.rs
 macro_rules! get_help {
-	($opt:ident) => {
-		if let Some(h) = $opt.help {
-	        format!("{}{}", h,
+	($opt:ident, $long:expr) => {
+		if let Some(h) = if $long { $opt.long_help.or($opt.help) } else { $opt.help } {
+	        format!("{}{}{}", h,
 	            if let Some(ref pv) = $opt.possible_vals {
 	                let mut pv_s = pv.iter().fold(String::with_capacity(50), |acc, name| acc + &format!(" {}",name)[..]);
 	                pv_s.shrink_to_fit();
 	                format!(" [values:{}]", &pv_s[..])
+	            }else{"".to_owned()},
+	            if let Some(dv) = $opt.default_val {
+	                format!(" [default: {}]", dv.to_string_lossy())
 	            }else{"".to_owned()})
 	    } else {
 	        "    ".to_owned()
@@ -14,35 +17,57 @@ macro_rules! get_help {
 	};
 }
 
+// De-duplication macro used in src/app.rs
+//
+// The spelling of an argument in error messages, which is its display name if one was set
+macro_rules! disp_name {
+	($arg:ident, $default:expr) => {
+		match $arg.disp_name {
+			Some(n) => n.to_owned(),
+			None    => $default
+		}
+	};
+}
+
+// De-duplication macro used in src/app.rs and src/man.rs
+macro_rules! deprecated_note {
+	($arg:ident) => {
+		match $arg.deprecated {
+			Some("")   => " [deprecated]".to_owned(),
+			Some(note) => format!(" [deprecated: {}]", note),
+			None       => String::new()
+		}
+	};
+}
+
+// De-duplication macro used in src/app.rs
+macro_rules! has_alias {
+	($arg:ident, $name:expr) => {
+		!$arg.strict && $arg.aliases.as_ref().map_or(false, |v| v.iter().any(|&(a, _)| a == $name))
+	};
+}
+
 // De-duplication macro used in src/app.rs
 macro_rules! parse_group_reqs {
 	($me:ident, $arg:ident) => {
-	    for ag in $me.groups.values() {
-	        let mut found = false;
-	        for name in ag.args.iter() {
-	            if name == &$arg.name {
-	                $me.required.remove(ag.name);
-		            if let Some(ref reqs) = ag.requires {
-		                for r in reqs {
-		                    $me.required.insert(r);
-		                }
-		            }
-		            if let Some(ref bl) = ag.conflicts {
-		                for b in bl {
-		                    $me.blacklist.insert(b);
-		                }
-		            }
-	                found = true;
-	                break;
+	    for g in $arg.groups.iter() {
+	        let ag = &$me.groups[*g];
+	        $me.required.remove(ag.name);
+	        if let Some(ref reqs) = ag.requires {
+	            for r in reqs {
+	                $me.required.insert(r);
 	            }
 	        }
-	        if found {
-	            for name in ag.args.iter() {
-	                if name == &$arg.name { continue }
-	                $me.required.remove(name);
-	                $me.blacklist.insert(name);
+	        if let Some(ref bl) = ag.conflicts {
+	            for b in bl {
+	                $me.blacklist.insert(b);
 	            }
-	        } 
+	        }
+	        for name in ag.args.iter() {
+	            if name == &$arg.name { continue }
+	            $me.required.remove(name);
+	            $me.blacklist.insert(name);
+	        }
 	    }
     };
 }
@@ -142,7 +167,7 @@ macro_rules! value_t {
 			Some(v) => {
 				match v.parse::<$t>() {
 					Ok(val) => Ok(val),
-					Err(_)  => Err(format!("{} isn't a valid {}",v,stringify!($t))),
+					Err(_)  => Err(format!("{} isn't a valid {}{}",v,stringify!($t),$m.value_help($v))),
 				}
 			},
 			None => Err(format!("Argument \"{}\" not found", $v))
@@ -150,14 +175,14 @@ macro_rules! value_t {
 	};
 	($m:ident.values_of($v:expr), $t:ty) => {
 		match $m.values_of($v) {
-			Some(ref v) => {
+			Some(v) => {
 				let mut tmp = Vec::with_capacity(v.len());
 				let mut err = None;
 				for pv in v {
 					match pv.parse::<$t>() {
 						Ok(rv) => tmp.push(rv),
 						Err(e) => {
-							err = Some(format!("{} isn't a valid {}\n{}",pv,stringify!($t),e));
+							err = Some(format!("{} isn't a valid {}\n{}{}",pv,stringify!($t),e,$m.value_help($v)));
 							break
 						}
 					}
@@ -174,7 +199,9 @@ macro_rules! value_t {
 
 /// Convenience macro getting a typed value `T` where `T` implements `std::str::FromStr`
 /// This macro returns a `T` or `Vec<T>` or exits with a usage string upon failure. This
-/// removes some of the boiler plate to handle failures from value_t! above. 
+/// removes some of the boiler plate to handle failures from value_t! above. The error is reported
+/// like any other, i.e. to the function set with `App::error_output()`, and exits with the status
+/// code set with `App::exit_code_on_error()`.
 ///
 /// You can use it to get a single value `T`, or a `Vec<T>` with the `values_of()`
 /// 
@@ -225,51 +252,117 @@ macro_rules! value_t_or_exit {
 				match v.parse::<$t>() {
 					Ok(val) => val,
 					Err(e)  => {
-						println!("{} isn't a valid {}\n{}\n{}\nPlease re-run with --help for more information",
+						$m.exit_with_error(&format!("{} isn't a valid {}\n{}{}",
 							v,
 							stringify!($t), 
 							e,
-							$m.usage());
-						::std::process::exit(1);
+							$m.value_help($v))[..]);
 					}
 				}
 			},
 			None => {
-				println!("Argument \"{}\" not found or is not valid\n{}\nPlease re-run with --help for more information",
-					$v, 
-					$m.usage());
-				::std::process::exit(1);
+				$m.exit_with_error(&format!("Argument \"{}\" not found or is not valid", $v)[..]);
 			}
 		}
 	};
 	($m:ident.values_of($v:expr), $t:ty) => {
 		match $m.values_of($v) {
-			Some(ref v) => {
+			Some(v) => {
 				let mut tmp = Vec::with_capacity(v.len());
 				for pv in v {
 					match pv.parse::<$t>() {
 						Ok(rv) => tmp.push(rv),
 						Err(_)  => {
-							println!("{} isn't a valid {}\n{}\nPlease re-run with --help for more information",
+							$m.exit_with_error(&format!("{} isn't a valid {}{}",
 								pv,
 								stringify!($t), 
-								$m.usage()); 
-							::std::process::exit(1);
+								$m.value_help($v))[..]);
 						}
 					}
 				}
 				tmp
 			},
 			None => {
-				println!("Argument \"{}\" not found or is not valid\n{}\nPlease re-run with --help for more information",
-					$v, 
-					$m.usage());
-				::std::process::exit(1);
+				$m.exit_with_error(&format!("Argument \"{}\" not found or is not valid", $v)[..]);
 			}
 		}
 	};
 }
 
+/// Dispatches on the subcommand used at runtime, expanding to the `match` over
+/// `ArgMatches::subcommand()` which otherwise has to be written out by hand. Each arm names a
+/// subcommand (which must be a string literal), the identifier its `ArgMatches` is bound to,
+/// and an expression. The optional final `_` arm is used when no subcommand (or one without an
+/// arm) was used. An arm repeating a name is a compile time error.
+///
+/// Arms may use the macro again to dispatch on nested subcommands.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use]
+/// # extern crate clap;
+/// # use clap::{App, SubCommand};
+/// # fn main() {
+/// let matches = App::new("myapp")
+///					.subcommand(SubCommand::new("build"))
+///					.subcommand(SubCommand::new("remote")
+///						.subcommand(SubCommand::new("add")))
+///					.get_matches();
+///
+/// match_subcommands!(matches,
+///		"build"  => |m| println!("building (verbose: {})", m.is_present("verbose")),
+///		"remote" => |m| match_subcommands!(m,
+///			"add" => |_m| println!("adding a remote"),
+///			_         => println!("listing the remotes")
+///		),
+///		_        => println!("nothing to do")
+///	);
+/// # }
+/// ```
+///
+/// Repeating a name doesn't compile
+///
+/// ```compile_fail
+/// # #[macro_use]
+/// # extern crate clap;
+/// # use clap::App;
+/// # fn main() {
+/// # let matches = App::new("myapp").get_matches();
+/// match_subcommands!(matches,
+///		"build" => |_m| println!("building"),
+///		"build" => |_m| println!("building again")
+///	);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! match_subcommands {
+	// The arms (and their names) are collected one at a time, so the final `_` arm can be told
+	// apart from a name
+	(@arms $m:expr, [$($arms:tt)*] [$($names:expr),*] _ => $default:expr) => {{
+		const _: () = $crate::assert_unique_subcommands(&[$($names),*]);
+		match $m.subcommand() {
+			$($arms)*
+			_ => $default
+		}
+	}};
+	(@arms $m:expr, [$($arms:tt)*] [$($names:expr),*] _ => $default:expr,) => {
+		match_subcommands!(@arms $m, [$($arms)*] [$($names),*] _ => $default)
+	};
+	(@arms $m:expr, [$($arms:tt)*] [$($names:expr),*]) => {
+		match_subcommands!(@arms $m, [$($arms)*] [$($names),*] _ => ())
+	};
+	(@arms $m:expr, [$($arms:tt)*] [$($names:expr),*] $name:tt => |$sm:ident| $body:expr) => {
+		match_subcommands!(@arms $m, [$($arms)* ($name, Some($sm)) => $body,] [$($names,)* $name])
+	};
+	(@arms $m:expr, [$($arms:tt)*] [$($names:expr),*] $name:tt => |$sm:ident| $body:expr, $($rest:tt)*) => {
+		match_subcommands!(@arms $m, [$($arms)* ($name, Some($sm)) => $body,] [$($names,)* $name] $($rest)*)
+	};
+	($m:expr, $($rest:tt)+) => {
+		match_subcommands!(@arms $m, [] [] $($rest)+)
+	};
+}
+
 /// Convenience macro generated a simple enum with variants to be used as a type when parsing
 /// arguments.
 ///
@@ -438,7 +531,8 @@ macro_rules! arg_enum {
 	};
 }
 
-/// Allows you pull the version for an from your Cargo.toml as MAJOR.MINOR.PATCH_PKGVERSION_PRE
+/// Allows you to pull the version for an app from your Cargo.toml at compile time as
+/// MAJOR.MINOR.PATCH (including any pre-release part), so it never drifts from the crate
 ///
 /// # Example
 /// ```no_run
@@ -454,10 +548,81 @@ macro_rules! arg_enum {
 #[macro_export]
 macro_rules! crate_version {
 	() => {
-		format!("{}.{}.{}{}",
-      		env!("CARGO_PKG_VERSION_MAJOR"),
-      		env!("CARGO_PKG_VERSION_MINOR"),
-      		env!("CARGO_PKG_VERSION_PATCH"),
-      		option_env!("CARGO_PKG_VERSION_PRE").unwrap_or(""))
+		env!("CARGO_PKG_VERSION")
 	}
 }
+
+/// Allows you to pull the authors for an app from your Cargo.toml. Cargo separates multiple
+/// authors with colons, which `App::author()` displays on separate lines (or with the separator
+/// set with `App::author_separator()`).
+///
+/// Optionally a separator may be given, in which case the authors are joined with it at runtime
+/// (split the same way as by `App::author()`, so URLs stay intact) and a `String` is returned
+/// instead of a `&'static str`
+///
+///
+/// # Example
+/// ```no_run
+/// # #[macro_use]
+/// # extern crate clap;
+/// # use clap::App;
+/// # fn main() {
+/// 	let m = App::new("app")
+///					.author(crate_authors!())
+///					.get_matches();
+///
+/// 	println!("Written by {}", crate_authors!(", "));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! crate_authors {
+	() => {
+		env!("CARGO_PKG_AUTHORS")
+	};
+	($sep:expr) => {
+		$crate::split_authors(env!("CARGO_PKG_AUTHORS")).join($sep)
+	};
+}
+
+/// Allows you to pull the description for an app from your Cargo.toml at compile time
+///
+/// # Example
+/// ```no_run
+/// # #[macro_use]
+/// # extern crate clap;
+/// # use clap::App;
+/// # fn main() {
+/// 	let m = App::new("app")
+///					.about(crate_description!())
+///					.get_matches();
+/// # }
+/// ```
+#[macro_export]
+macro_rules! crate_description {
+	() => {
+		env!("CARGO_PKG_DESCRIPTION")
+	}
+}
+
+/// Loads a YAML file relative to the current file at compile time, for use with
+/// `App::from_yaml()`
+///
+/// **NOTE:** This requires the `yaml` cargo feature
+///
+/// # Example
+/// ```ignore
+/// # #[macro_use]
+/// # extern crate clap;
+/// # use clap::App;
+/// # fn main() {
+/// 	let yml = load_yaml!("cli.yml");
+/// 	let m = App::from_yaml(yml).get_matches();
+/// # }
+/// ```
+#[cfg(feature = "yaml")]
+#[macro_export]
+macro_rules! load_yaml {
+	($yml:expr) => {
+		&$crate::YamlLoader::load_from_str(include_str!($yml)).ok().expect("failed to load YAML file")[0]
+	};
+}
@@ -0,0 +1,148 @@
+//! Generates man pages (in roff format) from an `App` definition
+//!
+//! This allows packagers to ship man pages derived from the same source as the auto-generated
+//! help, instead of maintaining them by hand.
+//!
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use clap::App;
+//! use std::fs::File;
+//!
+//! let app = App::new("myprog")
+//!                .version("1.0")
+//!                .about("Does awesome things")
+//!                .arg_from_usage("-c --config=[CONFIG] 'Sets a custom config file'");
+//! let mut f = File::create("myprog.1").unwrap();
+//! clap::man::generate(&app, &mut f).unwrap();
+//! ```
+use std::io::{self, Write};
+
+use app::App;
+
+/// Renders the NAME, SYNOPSIS, DESCRIPTION, OPTIONS, ARGS, SUBCOMMANDS, and AUTHOR sections of a
+/// man page for `app` in roff format into `w`. Sections without any content are omitted. Where
+/// detailed descriptions were set with `long_about()` or `long_help()`, those are used.
+///
+/// The automatically generated `help` and `version` flags are included, unless they have been
+/// overridden by the application.
+///
+///
+/// # Example
+///
+/// ```no_run
+/// # use clap::App;
+/// let app = App::new("myprog");
+/// clap::man::generate(&app, &mut std::io::stdout()).unwrap();
+/// ```
+pub fn generate<W: Write>(app: &App, w: &mut W) -> io::Result<()> {
+    try!(writeln!(w, ".TH {} 1 \"\" \"{}\"",
+        escape(&app.name.to_uppercase()[..]),
+        escape(&format!("{} {}", app.name, app.version.unwrap_or(""))[..]).trim()));
+
+    try!(writeln!(w, ".SH NAME"));
+    if let Some(about) = app.about {
+        try!(writeln!(w, "{} \\- {}", escape(&app.name[..]), escape(about)));
+    } else {
+        try!(writeln!(w, "{}", escape(&app.name[..])));
+    }
+
+    try!(writeln!(w, ".SH SYNOPSIS"));
+    try!(writeln!(w, "{}", escape(&app.create_usage_no_title()[..])));
+
+    let after = app.after_long_help.or(app.more_help);
+    if app.long_about.is_some() || after.is_some() {
+        try!(writeln!(w, ".SH DESCRIPTION"));
+    }
+    if let Some(a) = app.long_about {
+        try!(writeln!(w, "{}", escape(a)));
+    }
+    if let Some(h) = after {
+        try!(writeln!(w, "{}", escape(h)));
+    }
+
+    try!(writeln!(w, ".SH OPTIONS"));
+    // The same flags the usage string in the SYNOPSIS accounts for
+    let auto_flags = app.pending_auto_flags();
+    let mut flags = app.flags.values().chain(auto_flags.iter()).filter(|f| !f.hidden).collect::<Vec<_>>();
+    flags.sort_by_key(|f| f.name);
+    for f in flags {
+        try!(write_item(w, &switches(f.short, f.long)[..], f.long_help.or(f.help), &deprecated_note!(f)[..]));
+    }
+    for o in app.opts.values().filter(|o| !o.env_only() && !o.hidden) {
+        try!(write_item(w,
+            &format!("{} {}", switches(o.short, o.long), o.vals_usage())[..],
+            o.long_help.or(o.help), &deprecated_note!(o)[..]));
+    }
+
+    if app.positionals_idx.values().any(|p| !p.hidden) {
+        try!(writeln!(w, ".SH ARGS"));
+        for p in app.positionals_idx.values().filter(|p| !p.hidden) {
+            try!(write_item(w, &format!("{}", p)[..], p.long_help.or(p.help), &deprecated_note!(p)[..]));
+        }
+    }
+
+    if app.subcommands.values().any(|sc| !sc.hidden) {
+        try!(writeln!(w, ".SH SUBCOMMANDS"));
+        for sc in app.subcommands.values().filter(|sc| !sc.hidden) {
+            try!(write_item(w, &sc.name[..], sc.about, ""));
+        }
+    }
+
+    if app.author.is_some() {
+        try!(writeln!(w, ".SH AUTHOR"));
+        for (i, author) in app.authors().iter().enumerate() {
+            if i > 0 {
+                try!(writeln!(w, ".br"));
+            }
+            try!(writeln!(w, "{}", escape(author)));
+        }
+    }
+
+    Ok(())
+}
+
+// Writes a single tagged paragraph, i.e. an argument followed by its indented help text and any
+// note (such as a deprecation note)
+fn write_item<W: Write>(w: &mut W, tag: &str, help: Option<&str>, note: &str) -> io::Result<()> {
+    try!(writeln!(w, ".TP"));
+    try!(writeln!(w, "\\fB{}\\fR", escape(tag)));
+    let text = format!("{}{}", help.unwrap_or(""), note);
+    if !text.is_empty() {
+        try!(writeln!(w, "{}", escape(text.trim_left())));
+    }
+    Ok(())
+}
+
+// Formats the short and long switches of an argument as "-s, --long"
+fn switches(short: Option<char>, long: Option<&str>) -> String {
+    match (short, long) {
+        (Some(s), Some(l)) => format!("-{}, --{}", s, l),
+        (Some(s), None)    => format!("-{}", s),
+        (None, Some(l))    => format!("--{}", l),
+        (None, None)       => String::new()
+    }
+}
+
+// Escapes text so roff doesn't interpret it, i.e. backslashes, hyphens, and control characters
+// at the start of a line
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for line in s.lines() {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        if line.starts_with(".") || line.starts_with("'") {
+            out.push_str("\\&");
+        }
+        for c in line.chars() {
+            match c {
+                '\\' => out.push_str("\\e"),
+                '-'  => out.push_str("\\-"),
+                _    => out.push(c)
+            }
+        }
+    }
+    out
+}
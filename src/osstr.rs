@@ -0,0 +1,50 @@
+use std::ffi::{OsStr, OsString};
+#[cfg(unix)]
+use std::os::unix::ffi::OsStrExt;
+
+/// Splits a value, which may contain invalid UTF-8, at each occurrence of a delimiter. Values
+/// which are valid UTF-8 are split as usual, otherwise (on Unix) the raw bytes are split if the
+/// delimiter is ASCII, so the invalid parts are preserved as-is.
+pub fn split(val: &OsStr, delim: char) -> Vec<OsString> {
+    if let Some(s) = val.to_str() {
+        return s.split(delim).map(OsString::from).collect();
+    }
+    split_invalid(val, delim)
+}
+
+/// The part of a value following the first occurrence of an ASCII delimiter (i.e. the value of
+/// `--opt=value`), or `None` if the delimiter isn't found. The value may contain invalid UTF-8
+/// both before and after the delimiter.
+pub fn after(val: &OsStr, delim: char) -> Option<OsString> {
+    if let Some(s) = val.to_str() {
+        return s.find(delim).map(|i| OsString::from(&s[i + delim.len_utf8()..]));
+    }
+    after_invalid(val, delim)
+}
+
+#[cfg(unix)]
+fn split_invalid(val: &OsStr, delim: char) -> Vec<OsString> {
+    if (delim as u32) >= 0x80 {
+        return vec![val.to_os_string()];
+    }
+    val.as_bytes().split(|&b| b == delim as u8).map(|v| OsStr::from_bytes(v).to_os_string()).collect()
+}
+
+// There is no way to get at the raw value on other platforms, so it can only be split lossily
+#[cfg(not(unix))]
+fn split_invalid(val: &OsStr, delim: char) -> Vec<OsString> {
+    val.to_string_lossy().split(delim).map(OsString::from).collect()
+}
+
+#[cfg(unix)]
+fn after_invalid(val: &OsStr, delim: char) -> Option<OsString> {
+    let bytes = val.as_bytes();
+    bytes.iter().position(|&b| b as u32 == delim as u32).map(|i| OsStr::from_bytes(&bytes[i + 1..]).to_os_string())
+}
+
+// The lossy conversion only replaces the invalid parts, so the delimiter is found all the same
+#[cfg(not(unix))]
+fn after_invalid(val: &OsStr, delim: char) -> Option<OsString> {
+    let s = val.to_string_lossy();
+    s.find(delim).map(|i| OsString::from(&s[i + delim.len_utf8()..]))
+}
@@ -0,0 +1,293 @@
+use app::App;
+use args::OptBuilder;
+use quote::quote;
+
+/// What a `PartialParser` expects to be supplied next, see `PartialParser::expected()`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Expected<'p> {
+    /// The option with the given name still requires a value
+    Value(&'p str),
+    /// The option with the given name accepts further values, but may also be followed by any
+    /// other argument
+    MoreValues(&'p str),
+    /// The positional argument with the given name is next, unless a flag, option, or subcommand
+    /// is used
+    Positional(&'p str),
+    /// Any flag, option, or subcommand
+    Argument
+}
+
+/// A parser which is fed the arguments one at a time (i.e. as they're typed in an interactive
+/// shell), and can be asked for its current state in between. Unlike `App::get_matches()` it
+/// never exits the process, instead an argument which isn't valid is reported back and otherwise
+/// ignored. It's created with `App::partial_parser()`.
+///
+/// **NOTE:** Only the arguments themselves are checked, i.e. conflicts and requirements between
+/// arguments are left to the full parse once the command line is complete.
+///
+///
+/// # Example
+///
+/// ```no_run
+/// # use clap::{App, Arg, SubCommand, Expected};
+/// let mut app = App::new("myprog")
+///                   .subcommand(SubCommand::new("remote")
+///                       .arg(Arg::with_name("name")
+///                           .long("name")
+///                           .takes_value(true)));
+/// let mut parser = app.partial_parser();
+///
+/// parser.push("remote").unwrap();
+/// parser.push("--name").unwrap();
+/// assert_eq!(parser.subcommand(), Some("remote"));
+/// assert_eq!(parser.expected(), Expected::Value("name"));
+/// assert!(parser.push("--nmae").is_err());
+/// ```
+pub struct PartialParser<'p, 'a: 'p, 'v: 'p, 'ab: 'p, 'u: 'p, 'h: 'p, 'ar: 'p> {
+    // The app and the subcommands used so far, the last one being the one currently parsed
+    cmds: Vec<&'p App<'a, 'v, 'ab, 'u, 'h, 'ar>>,
+    // The option still requiring values, and how many
+    needs_val_of: Option<(&'ar str, u8)>,
+    pos_counter: u8,
+    pos_only: bool,
+    // The names of the arguments of the current subcommand used so far
+    seen: Vec<&'ar str>
+}
+
+impl<'p, 'a, 'v, 'ab, 'u, 'h, 'ar> PartialParser<'p, 'a, 'v, 'ab, 'u, 'h, 'ar> {
+    #[doc(hidden)]
+    pub fn new(app: &'p App<'a, 'v, 'ab, 'u, 'h, 'ar>) -> PartialParser<'p, 'a, 'v, 'ab, 'u, 'h, 'ar> {
+        PartialParser {
+            cmds: vec![app],
+            needs_val_of: None,
+            pos_counter: 1,
+            pos_only: false,
+            seen: vec![]
+        }
+    }
+
+    /// Feeds the next argument to the parser. If the argument isn't valid at this point, the
+    /// error message is returned and the state of the parser is left unchanged.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # let mut app = App::new("myprog");
+    /// let mut parser = app.partial_parser();
+    /// if let Err(e) = parser.push("--verbsoe") {
+    ///     println!("{}", e);
+    /// }
+    /// ```
+    pub fn push(&mut self, arg: &str) -> Result<(), String> {
+        let app = self.app();
+        if !self.pos_only {
+            if let Some((nvo, left)) = self.needs_val_of {
+                let opt = &app.opts[nvo];
+                // "--" ends the values, unless the option takes it as a value
+                if arg == "--" && !opt.sep_as_val && left > 0 {
+                    return Err(format!("Argument {} requires a value but none was supplied", disp_name!(opt, format!("\"{}\"", nvo))));
+                }
+                if left > 0 || !arg.starts_with("-") || arg.len() == 1 || (arg == "--" && opt.sep_as_val) {
+                    try!(self.check_possible_val(opt, arg));
+                    let left = left.saturating_sub(opt.split_vals(arg.as_ref()).len() as u8);
+                    self.needs_val_of = if left == 0 && !opt.multiple_vals { None } else { Some((nvo, left)) };
+                    return Ok(());
+                }
+            }
+            if arg == "--" {
+                self.needs_val_of = None;
+                self.pos_only = true;
+                if let Some(p) = app.positionals_idx.values().filter(|p| p.last).next() {
+                    self.pos_counter = p.index;
+                }
+                return Ok(());
+            }
+            if arg.starts_with("--") {
+                return self.push_long(&arg[2..]);
+            }
+            if arg.starts_with("-") && arg.len() != 1 {
+                return self.push_short(&arg[1..]);
+            }
+            if let Some(sc) = app.subcommands.get(arg) {
+                self.cmds.push(sc);
+                self.needs_val_of = None;
+                self.pos_counter = 1;
+                self.seen.clear();
+                return Ok(());
+            }
+        }
+        self.needs_val_of = None;
+        match app.positionals_idx.get(&self.pos_counter) {
+            Some(p) => {
+                if let Some(ref p_vals) = p.possible_vals {
+                    if !p_vals.is_empty() && !p_vals.contains(arg) {
+                        return Err(format!("{} isn't a valid value for {}", quote(arg), disp_name!(p, format!("{}", p))));
+                    }
+                }
+                self.seen.push(p.name);
+                if !p.multiple {
+                    self.pos_counter += 1;
+                }
+                Ok(())
+            },
+            None => Err(format!("Argument {} isn't a valid option for {}", quote(arg), self.cmds.iter().map(|c| &c.name[..]).collect::<Vec<_>>().join(" ")))
+        }
+    }
+
+    // Handles a long flag or option without its leading "--", including a value following '='
+    fn push_long(&mut self, arg: &str) -> Result<(), String> {
+        let app = self.app();
+        let (long, val) = match arg.find('=') {
+            Some(i) => (&arg[..i], Some(&arg[i + 1..])),
+            None    => (arg, None)
+        };
+        if let Some(opt) = app.opts.values().filter(|o| o.long == Some(long) || has_alias!(o, long)).next() {
+            self.needs_val_of = match val {
+                Some(v) => {
+                    try!(self.check_possible_val(opt, v));
+                    if opt.multiple_vals { Some((opt.name, 0)) } else { None }
+                },
+                None => Some((opt.name, opt.num_vals.unwrap_or(1)))
+            };
+            self.seen.push(opt.name);
+            return Ok(());
+        }
+        if let Some(flag) = app.flags.values().filter(|f| f.long == Some(long) || has_alias!(f, long)).next() {
+            if val.is_some() {
+                return Err(format!("The flag {} doesn't take a value", quote(&format!("--{}", long)[..])));
+            }
+            self.seen.push(flag.name);
+            self.needs_val_of = None;
+            return Ok(());
+        }
+        Err(format!("The argument {} isn't valid", quote(&format!("--{}", long)[..])))
+    }
+
+    // Handles a short flag or cluster of short flags without its leading '-', the last of which
+    // may be an option taking the next argument as its value, as the parser does
+    fn push_short(&mut self, arg: &str) -> Result<(), String> {
+        let app = self.app();
+        if let Some((_, msg)) = app.short_cluster_error(arg) {
+            return Err(msg);
+        }
+        self.needs_val_of = None;
+        for c in arg.chars() {
+            if let Some(flag) = app.flags.values().filter(|f| f.short == Some(c)).next() {
+                self.seen.push(flag.name);
+            } else if let Some(opt) = app.opts.values().filter(|o| o.short == Some(c)).next() {
+                self.seen.push(opt.name);
+                self.needs_val_of = Some((opt.name, opt.num_vals.unwrap_or(1)));
+            }
+        }
+        Ok(())
+    }
+
+    // Checks the (possibly delimited) value against the possible values of the option
+    fn check_possible_val(&self, opt: &OptBuilder, val: &str) -> Result<(), String> {
+        if let Some(ref p_vals) = opt.possible_vals {
+            if !p_vals.is_empty() {
+                if let Some(v) = opt.split_vals(val.as_ref()).iter().map(|v| v.to_string_lossy().into_owned()).filter(|v| !p_vals.contains(&v[..])).next() {
+                    return Err(format!("{} isn't a valid value for {}", quote(&v[..]), disp_name!(opt, opt.switch())));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn app(&self) -> &'p App<'a, 'v, 'ab, 'u, 'h, 'ar> {
+        // There's always at least the app itself
+        self.cmds[self.cmds.len() - 1]
+    }
+
+    /// Returns what the parser expects to be supplied next.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap::{App, Expected};
+    /// # let mut app = App::new("myprog");
+    /// let parser = app.partial_parser();
+    /// if let Expected::Value(name) = parser.expected() {
+    ///     println!("a value for {} is required", name);
+    /// }
+    /// ```
+    pub fn expected(&self) -> Expected<'ar> {
+        match self.needs_val_of {
+            Some((name, 0)) => Expected::MoreValues(name),
+            Some((name, _)) => Expected::Value(name),
+            None => match self.app().positionals_idx.get(&self.pos_counter) {
+                Some(p) => Expected::Positional(p.name),
+                None    => Expected::Argument
+            }
+        }
+    }
+
+    /// Returns the likely candidates for the next argument, i.e. to be offered as hints while
+    /// typing. These are the possible values (or the value candidates) of the option or
+    /// positional argument expected next, or else the subcommands and the long versions of the
+    /// flags and options.
+    pub fn candidates(&self) -> Vec<String> {
+        let app = self.app();
+        let (p_vals, candidates) = match self.expected() {
+            Expected::Value(name) | Expected::MoreValues(name) => {
+                let o = &app.opts[name];
+                (o.possible_vals.as_ref(), o.candidates)
+            },
+            Expected::Positional(name) => {
+                let p = app.positionals_idx.values().filter(|p| p.name == name).next().unwrap();
+                (p.possible_vals.as_ref(), p.candidates)
+            },
+            Expected::Argument => (None, None)
+        };
+        if let Some(p_vals) = p_vals {
+            if !p_vals.is_empty() {
+                return p_vals.iter().map(|v| v.to_string()).collect();
+            }
+        }
+        if let Some(f) = candidates {
+            return f();
+        }
+        if let Expected::Value(_) = self.expected() {
+            return vec![];
+        }
+        let mut c = app.subcommands.values().filter(|sc| !sc.hidden).map(|sc| sc.name.clone()).collect::<Vec<_>>();
+        c.extend(app.flags.values().filter(|f| !f.hidden).filter_map(|f| f.long).map(|l| format!("--{}", l)));
+        c.extend(app.opts.values().filter(|o| !o.hidden).filter_map(|o| o.long).map(|l| format!("--{}", l)));
+        c
+    }
+
+    /// Returns the name of the subcommand currently being parsed, if any.
+    pub fn subcommand(&self) -> Option<&str> {
+        if self.cmds.len() > 1 {
+            Some(&self.app().name[..])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the names of all subcommands used so far, from the outermost to the innermost.
+    pub fn subcommand_path(&self) -> Vec<&str> {
+        self.cmds.iter().skip(1).map(|c| &c.name[..]).collect()
+    }
+
+    /// Returns `true` if the arguments supplied so far could be the complete command line, i.e.
+    /// no option is waiting for a value, and all required arguments (and a required subcommand)
+    /// of the current subcommand were supplied.
+    pub fn is_complete(&self) -> bool {
+        let app = self.app();
+        if let Some((_, left)) = self.needs_val_of {
+            if left > 0 {
+                return false;
+            }
+        }
+        if app.subcmd_required && app.subcommands.keys().any(|n| n != "help") {
+            return false;
+        }
+        let mut required = app.opts.values().filter(|o| o.required).map(|o| o.name)
+                              .chain(app.positionals_idx.values().filter(|p| p.required).map(|p| p.name));
+        required.all(|n| self.seen.contains(&n))
+    }
+}
@@ -0,0 +1,27 @@
+/// Quotes a user supplied value so it can be echoed in a message, and copy-pasted back into a
+/// POSIX shell, without being split at whitespace, expanded, or corrupting the terminal.
+///
+/// Values are wrapped in single quotes (with any embedded single quotes escaped as `'\''`),
+/// unless they contain control characters, in which case the ANSI-C `$'...'` form is used so
+/// those characters are displayed as escape sequences instead of being written raw.
+pub fn quote(s: &str) -> String {
+    if !s.chars().any(|c| c.is_control()) {
+        return format!("'{}'", s.replace("'", "'\\''"));
+    }
+    let mut out = String::with_capacity(s.len() + 3);
+    out.push_str("$'");
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\''  => out.push_str("\\'"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() && (c as u32) < 0x80 => out.push_str(&format!("\\x{:02x}", c as u32)[..]),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)[..]),
+            c    => out.push(c)
+        }
+    }
+    out.push('\'');
+    out
+}
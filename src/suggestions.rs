@@ -0,0 +1,38 @@
+use std::cmp;
+use std::mem;
+
+// The maximum edit distance at which a possible value is still considered a likely typo
+const MAX_DISTANCE: usize = 2;
+
+/// Computes the Levenshtein distance (the number of single character insertions, deletions, or
+/// substitutions needed to turn one into the other) between two strings
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..b_chars.len() + 1).collect();
+    let mut cur = vec![0; b_chars.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b_chars.iter().enumerate() {
+            let sub = prev[j] + if ca == *cb { 0 } else { 1 };
+            cur[j + 1] = cmp::min(sub, cmp::min(prev[j + 1], cur[j]) + 1);
+        }
+        mem::swap(&mut prev, &mut cur);
+    }
+    prev[b_chars.len()]
+}
+
+/// Returns the closest match to `v` out of `possible_values`, so long as it's close enough to
+/// likely be a typo
+pub fn did_you_mean<'a, T>(v: &str, possible_values: T) -> Option<&'a str>
+                           where T: IntoIterator<Item = &'a str> {
+    let mut candidate = None;
+    let mut best = MAX_DISTANCE + 1;
+    for pv in possible_values {
+        let d = levenshtein(v, pv);
+        if d < best {
+            best = d;
+            candidate = Some(pv);
+        }
+    }
+    candidate
+}
@@ -0,0 +1,40 @@
+//! Reports how long each phase of parsing takes, in order to detect regressions in the startup
+//! latency of a command line program (i.e. in benchmarks).
+//!
+//! **NOTE:** This requires the `timings` cargo feature
+//!
+//!
+//! # Example
+//!
+//! ```ignore
+//! # use clap::App;
+//! use clap::timings::Phase;
+//! use std::time::Duration;
+//!
+//! fn report(phase: Phase, took: Duration) {
+//!     println!("{:?} took {:?}", phase, took);
+//! }
+//!
+//! let matches = App::new("myprog")
+//!                   .timing_hook(report)
+//!                   .get_matches();
+//! ```
+use std::time::Duration;
+
+/// The phases of parsing which are timed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    /// Reading the arguments from the environment and determining the binary name
+    Tokenizing,
+    /// Matching each argument to the possible flags, options, positional arguments, and
+    /// subcommands (reported once per `App` or `SubCommand`)
+    Matching,
+    /// Validating the matches against the requirement and conflict rules (reported once per
+    /// `App` or `SubCommand`)
+    Validating,
+    /// Generating the help information
+    HelpGeneration
+}
+
+/// A function which is called with each phase and the time it took
+pub type TimingHook = fn(Phase, Duration);
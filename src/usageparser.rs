@@ -1,4 +1,5 @@
-use std::str::Chars;
+use std::iter::Peekable;
+use std::str::CharIndices;
 
 pub enum UsageToken<'u> {
     Name(&'u str, Option<bool>),
@@ -8,24 +9,28 @@ pub enum UsageToken<'u> {
     Multiple,
 }
 
+// Positions are tracked as byte offsets (instead of counting chars) so that usage strings
+// containing multi-byte characters (i.e. a short such as `-ä`) are sliced correctly
 pub struct UsageParser<'u> {
     usage: &'u str,
-    chars: Chars<'u>,
-    s: usize,
-    e: usize,
+    chars: Peekable<CharIndices<'u>>,
 }
 
 impl<'u> UsageParser<'u> {
     pub fn with_usage(u: &'u str) -> UsageParser<'u> {
         UsageParser {
             usage: u,
-            chars: u.chars(),
-            s: 0,
-            e: 0,
+            chars: u.char_indices().peekable(),
         }
     }
 
- 
+    // The byte offset of the next char, or the end of the usage string if there are no more
+    fn pos(&mut self) -> usize {
+        match self.chars.peek() {
+            Some(&(i, _)) => i,
+            None          => self.usage.len()
+        }
+    }
 }
 
 impl<'u> Iterator for UsageParser<'u> {
@@ -34,64 +39,48 @@ impl<'u> Iterator for UsageParser<'u> {
     fn next(&mut self) -> Option<UsageToken<'u>> {
         loop {
             match self.chars.next() {
-                Some(c) if c == '[' || c == '<'  => {
-                    // self.s = self.e + 1;
-                    if self.e != 0 {
-                        self.e += 1;
-                    }
-                    self.s = self.e + 1;
+                Some((_, c)) if c == '[' || c == '<'  => {
                     let closing = match c {
                         '[' => ']',
                         '<' => '>',
                         _   => unreachable!()
                     };
-                    while let Some(c) =  self.chars.next() {
-                        self.e += 1;
-                        if c == closing { break }
-                    } 
-                    if self.e > self.usage.len() { return None }
-
-                    // self.e += 1;
-
-                    let name = &self.usage[self.s..self.e];
+                    let s = self.pos();
+                    let mut e = None;
+                    while let Some((i, n)) = self.chars.next() {
+                        if n == closing { e = Some(i); break }
+                    }
+                    let e = match e {
+                        Some(e) => e,
+                        None    => return None
+                    };
 
-                    
-                    return Some(UsageToken::Name(name, if c == '<' { Some(true)} else {None}));
+                    return Some(UsageToken::Name(&self.usage[s..e], if c == '<' { Some(true)} else {None}));
                 },
-                Some('\'') => {
-                    self.s = self.e + 2;
-                    self.e = self.usage.len() - 1;
-                    
+                Some((i, '\'')) => {
+                    // The help runs until the closing quote, which is the last char
+                    let s = i + 1;
+                    let e = match self.usage[s..].char_indices().last() {
+                        Some((j, _)) => s + j,
+                        None         => s
+                    };
                     while let Some(_) = self.chars.next() { continue }
 
-                    return Some(UsageToken::Help(&self.usage[self.s..self.e]));
+                    return Some(UsageToken::Help(&self.usage[s..e]));
                 },
-                Some('-')  => {
-                    self.e += 1;
+                Some((_, '-'))  => {
                     match self.chars.next() {
-                        Some('-') => {
-                            if self.e != 1 {
-                                self.e += 1;
+                        Some((_, '-')) => {
+                            let s = self.pos();
+                            let mut e = self.usage.len();
+                            while let Some((i, c)) = self.chars.next() {
+                                // longs consume one '.' of a following '...'
+                                if c == ' ' || c == '=' || c == '.' { e = i; break }
                             }
-                            
-                            self.s = self.e + 1;
-
-                            while let Some(c) = self.chars.next() {
-                                self.e += 1;
-                                if c == ' ' || c == '=' || c == '.' { break }
-                            } 
-                            if self.e > self.usage.len() { return None }
 
-                            if self.e == self.usage.len() - 1 {
-                                return Some(UsageToken::Long(&self.usage[self.s..]))
-                            }
-                            return Some(UsageToken::Long(&self.usage[self.s..self.e]))
+                            return Some(UsageToken::Long(&self.usage[s..e]))
                         },
-                        Some(c)  => {
-                            // When short is first don't increment e
-                            if self.e != 1 {
-                                self.e += 1;
-                            }
+                        Some((_, c))  => {
                             // Short
                             if !c.is_alphanumeric() {
                                 return None
@@ -103,29 +92,23 @@ impl<'u> Iterator for UsageParser<'u> {
                         }
                     }
                 },
-                Some('.') => {
-                    self.e += 1;
+                Some((_, '.')) => {
+                    // longs consume one '.' so they match '.. ' whereas shorts can match '...'
                     let mut mult = false;
                     for _ in 0..2 {
-                        self.e += 1;
-                        match self.chars.next() {
-                            // longs consume one '.' so they match '.. ' whereas shorts can match '...'
-                            Some('.') | Some(' ')  => { mult = true; },
-                            _          => { 
-                                // if there is no help or following space all we can match is '..'
-                                if self.e == self.usage.len() - 1 {
-                                    mult = true;
-                                }
-                                break; 
-                            }
+                        match self.chars.peek() {
+                            Some(&(_, '.')) | Some(&(_, ' ')) => { mult = true; },
+                            // if there is no help or following space all we can match is '..'
+                            None => { mult = true; break; },
+                            _    => break
                         }
+                        self.chars.next();
                     }
                     if mult { return Some(UsageToken::Multiple) }
                 },
-                Some(' ') | Some('=') | Some(']') | Some('>') | Some('\t') => {
-                    self.e += 1;
+                Some((_, ' ')) | Some((_, '=')) | Some((_, ']')) | Some((_, '>')) | Some((_, '\t')) => {
                     continue
-                },                
+                },
                 _  => {
                     return None
                 }